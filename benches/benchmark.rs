@@ -79,8 +79,8 @@ fn criterion_compare_order_benchmark(c: &mut Criterion) {
                     for x in 0..row {
                         for y in 0..row {
                             hilbert_curve.point_to_index(CoordinateValue {
-                                x: black_box(x as u32),
-                                y: black_box(y as u32),
+                                x: black_box(x),
+                                y: black_box(y),
                             });
                         }
                     }
@@ -90,11 +90,11 @@ fn criterion_compare_order_benchmark(c: &mut Criterion) {
         group.bench_with_input(
             BenchmarkId::new("fast_hilbert", order),
             &order,
-            |b, order| {
+            |b, _order| {
                 b.iter(|| {
                     for x in 0..row {
                         for y in 0..row {
-                            fast_hilbert::xy2h(black_box(x as u32), black_box(y as u32));
+                            fast_hilbert::xy2h(black_box(x), black_box(y));
                         }
                     }
                 })
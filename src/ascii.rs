@@ -0,0 +1,101 @@
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+
+const MAXIMUM_ASCII_ORDER: u16 = 6;
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Draw the path of the curve with box-drawing characters, one line per row with the row 0 at the top.
+pub fn render_ascii(hilbert_curve: &HilbertCurveAlgorithm) -> String {
+    let number_of_rows = ascii_number_of_rows(hilbert_curve);
+    let mut connections = vec![0u8; (number_of_rows * number_of_rows) as usize];
+    let position = |x: u32, y: u32| (y * number_of_rows + x) as usize;
+    let mut previous = hilbert_curve.index_to_point(0);
+    for index in 1..number_of_rows * number_of_rows {
+        let current = hilbert_curve.index_to_point(index);
+        let (from, to) = if current.x > previous.x {
+            (RIGHT, LEFT)
+        } else if current.x < previous.x {
+            (LEFT, RIGHT)
+        } else if current.y > previous.y {
+            (DOWN, UP)
+        } else {
+            (UP, DOWN)
+        };
+        connections[position(previous.x, previous.y)] |= from;
+        connections[position(current.x, current.y)] |= to;
+        previous = current;
+    }
+
+    let mut text = String::new();
+    for y in 0..number_of_rows {
+        for x in 0..number_of_rows {
+            let connection = connections[position(x, y)];
+            text.push(box_character(connection));
+            if x + 1 < number_of_rows {
+                text.push(if connection & RIGHT != 0 { '─' } else { ' ' });
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Write the Hilbert index of each cell, right aligned, one line per row with the row 0 at the top.
+pub fn render_ascii_indices(hilbert_curve: &HilbertCurveAlgorithm) -> String {
+    let number_of_rows = ascii_number_of_rows(hilbert_curve);
+    let width = (number_of_rows * number_of_rows - 1).to_string().len();
+    let mut rows = vec![vec![0u32; number_of_rows as usize]; number_of_rows as usize];
+    for index in 0..number_of_rows * number_of_rows {
+        let point = hilbert_curve.index_to_point(index);
+        rows[point.y as usize][point.x as usize] = index;
+    }
+    let mut text = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|index| format!("{:>width$}", index, width = width))
+            .collect();
+        text.push_str(&cells.join(" "));
+        text.push('\n');
+    }
+    text
+}
+
+fn ascii_number_of_rows(hilbert_curve: &HilbertCurveAlgorithm) -> u32 {
+    if hilbert_curve.order() > MAXIMUM_ASCII_ORDER {
+        panic!("The order is too high to render the curve as text. Use an order of 6 or less.");
+    }
+    hilbert_curve.number_of_rows()
+}
+
+fn box_character(connection: u8) -> char {
+    match connection {
+        c if c == LEFT | RIGHT => '─',
+        c if c == UP | DOWN => '│',
+        c if c == DOWN | RIGHT => '┌',
+        c if c == DOWN | LEFT => '┐',
+        c if c == UP | RIGHT => '└',
+        c if c == UP | LEFT => '┘',
+        RIGHT => '╶',
+        LEFT => '╴',
+        UP => '╵',
+        DOWN => '╷',
+        _ => '·',
+    }
+}
+
+#[cfg(test)]
+mod test_box_character {
+    use super::*;
+
+    #[test]
+    fn test_box_character_corners_and_ends() {
+        assert_eq!('┌', box_character(DOWN | RIGHT));
+        assert_eq!('┘', box_character(UP | LEFT));
+        assert_eq!('╷', box_character(DOWN));
+        assert_eq!('·', box_character(0));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::rectangle::Rectangle;
+use crate::region_coverer::{BlockRelation, CoverableRegion};
+
+/// Disk in grid units: the cell `(x, y)` is the unit square from `(x, y)` to `(x + 1, y + 1)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Circle {
+    center: (f64, f64),
+    radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: (f64, f64), radius: f64) -> Self {
+        if !(center.0.is_finite() && center.1.is_finite() && radius.is_finite() && radius >= 0.0) {
+            panic!("The center must be finite and the radius finite and positive");
+        }
+        Self { center, radius }
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        self.center
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let (dx, dy) = (x - self.center.0, y - self.center.1);
+        dx * dx + dy * dy <= self.radius * self.radius
+    }
+}
+
+impl CoverableRegion for Circle {
+    /// Cells only touching the circle are outside.
+    fn relation(&self, block: &Rectangle) -> BlockRelation {
+        let (x_min, y_min) = (f64::from(block.x_min), f64::from(block.y_min));
+        let (x_max, y_max) = (f64::from(block.x_max) + 1.0, f64::from(block.y_max) + 1.0);
+        let (x, y) = self.center;
+        let nearest_x = x.clamp(x_min, x_max) - x;
+        let nearest_y = y.clamp(y_min, y_max) - y;
+        let farthest_x = (x - x_min).abs().max((x_max - x).abs());
+        let farthest_y = (y - y_min).abs().max((y_max - y).abs());
+        let squared_radius = self.radius * self.radius;
+        if nearest_x * nearest_x + nearest_y * nearest_y >= squared_radius {
+            BlockRelation::Outside
+        } else if farthest_x * farthest_x + farthest_y * farthest_y <= squared_radius {
+            BlockRelation::Inside
+        } else {
+            BlockRelation::Partial
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Lossless grayscale codec following the Hilbert curve.
+//!
+//! The pixels are read along the padded Hilbert scan of the image, so consecutive pixels are neighbors
+//! in the image. Each pixel is stored as its difference with the previous one, and the differences are
+//! written as LEB128 varint tokens: a nonzero difference `d` is `zigzag(d) << 1`, a run of `n` zero
+//! differences is `(n - 1) << 1 | 1`.
+//!
+//! Layout: magic `HGC1`, width and height as little-endian `u32`, curve order as one byte, then the tokens.
+//! Images hold at most `MAXIMUM_PIXEL_COUNT` pixels.
+use crate::image_scan::{for_each_scan_position, from_hilbert_scan, order_for_size, ScanMode};
+
+const CODEC_MAGIC: &[u8; 4] = b"HGC1";
+const HEADER_LEN: usize = 13;
+const MAXIMUM_ORDER: u16 = 16;
+
+pub const MAXIMUM_PIXEL_COUNT: u64 = 1 << 30;
+
+pub fn encode_gray(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = header(pixels, width, height);
+    let mut scan = Vec::with_capacity(pixels.len());
+    for_each_scan_position(width, height, ScanMode::Padded, |position| {
+        scan.push(pixels[position])
+    });
+    encode_residuals(&scan, &mut bytes);
+    bytes
+}
+
+/// Same tokens as `encode_gray` read along the rows instead of the curve, to compare the two scans.
+#[doc(hidden)]
+pub fn encode_gray_row_major(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = header(pixels, width, height);
+    encode_residuals(pixels, &mut bytes);
+    bytes
+}
+
+/// Pixels, width and height of an image written by `encode_gray`.
+pub fn decode_gray(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32), String> {
+    if bytes.len() < HEADER_LEN {
+        return Err("The buffer is truncated".to_string());
+    }
+    if &bytes[0..4] != CODEC_MAGIC {
+        return Err("The buffer is not a Hilbert grayscale image".to_string());
+    }
+    let width = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let height = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+    let order = u16::from(bytes[12]);
+    if order > MAXIMUM_ORDER {
+        return Err("The curve order must be 16 or less".to_string());
+    }
+    if order != order_for_size(width.max(height)) {
+        return Err("The curve order does not match the dimensions".to_string());
+    }
+    let pixel_count = u64::from(width) * u64::from(height);
+    if pixel_count > MAXIMUM_PIXEL_COUNT {
+        return Err("The image has too many pixels".to_string());
+    }
+    let pixel_count =
+        usize::try_from(pixel_count).map_err(|_| "The image is too large".to_string())?;
+    let scan = decode_residuals(&bytes[HEADER_LEN..], pixel_count)?;
+    let pixels = from_hilbert_scan(&scan, width, height, ScanMode::Padded);
+    Ok((pixels, width, height))
+}
+
+fn header(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    if pixels.len() as u64 != u64::from(width) * u64::from(height) {
+        panic!("The number of pixels must be the width times the height");
+    }
+    if pixels.len() as u64 > MAXIMUM_PIXEL_COUNT
+        || order_for_size(width.max(height)) > MAXIMUM_ORDER
+    {
+        panic!("The image must hold at most 2^30 pixels and be at most 65536 pixels wide and high");
+    }
+    let mut bytes = Vec::with_capacity(HEADER_LEN + pixels.len() / 2);
+    bytes.extend_from_slice(CODEC_MAGIC);
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.push(order_for_size(width.max(height)) as u8);
+    bytes
+}
+
+fn encode_residuals(scan: &[u8], bytes: &mut Vec<u8>) {
+    let mut previous = 0u8;
+    let mut zero_run: u64 = 0;
+    for pixel in scan {
+        let residual = pixel.wrapping_sub(previous) as i8;
+        previous = *pixel;
+        if residual == 0 {
+            zero_run += 1;
+            continue;
+        }
+        if zero_run > 0 {
+            write_varint(bytes, (zero_run - 1) << 1 | 1);
+            zero_run = 0;
+        }
+        write_varint(bytes, u64::from(zigzag(residual)) << 1);
+    }
+    if zero_run > 0 {
+        write_varint(bytes, (zero_run - 1) << 1 | 1);
+    }
+}
+
+fn decode_residuals(mut bytes: &[u8], pixel_count: usize) -> Result<Vec<u8>, String> {
+    let mut scan = Vec::with_capacity(pixel_count.min(bytes.len().saturating_mul(64)));
+    let mut previous = 0u8;
+    while !bytes.is_empty() {
+        let token = read_varint(&mut bytes)?;
+        if token & 1 == 1 {
+            let run = (token >> 1).saturating_add(1);
+            if run > (pixel_count - scan.len()) as u64 {
+                return Err("A run of pixels goes past the last pixel".to_string());
+            }
+            scan.resize(scan.len() + run as usize, previous);
+        } else {
+            let value =
+                u8::try_from(token >> 1).map_err(|_| "A difference is out of range".to_string())?;
+            if scan.len() == pixel_count {
+                return Err("There are unexpected bytes after the last pixel".to_string());
+            }
+            previous = previous.wrapping_add(unzigzag(value) as u8);
+            scan.push(previous);
+        }
+    }
+    if scan.len() != pixel_count {
+        return Err("The buffer is truncated".to_string());
+    }
+    Ok(scan)
+}
+
+fn zigzag(value: i8) -> u8 {
+    ((value << 1) ^ (value >> 7)) as u8
+}
+
+fn unzigzag(value: u8) -> i8 {
+    ((value >> 1) as i8) ^ -((value & 1) as i8)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, String> {
+    let mut value: u64 = 0;
+    for (position, byte) in bytes.iter().enumerate() {
+        // The tenth byte holds the last bit of a `u64`
+        if position == 9 && *byte > 1 {
+            return Err("A varint is too long".to_string());
+        }
+        value |= u64::from(byte & 0x7f) << (7 * position);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[position + 1..];
+            return Ok(value);
+        }
+    }
+    Err("A varint is truncated".to_string())
+}
+
+#[cfg(test)]
+mod test_zigzag {
+    use super::*;
+
+    #[test]
+    fn test_zigzag() {
+        assert_eq!(0, zigzag(0));
+        assert_eq!(1, zigzag(-1));
+        assert_eq!(2, zigzag(1));
+        assert_eq!(255, zigzag(-128));
+        for value in i8::MIN..=i8::MAX {
+            assert_eq!(value, unzigzag(zigzag(value)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_varint {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            let mut slice = bytes.as_slice();
+            assert_eq!(Ok(value), read_varint(&mut slice));
+            assert!(slice.is_empty());
+        }
+        let mut truncated: &[u8] = &[0x80, 0x80];
+        assert_eq!(
+            Err("A varint is truncated".to_string()),
+            read_varint(&mut truncated)
+        );
+        let mut overflowing: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert_eq!(
+            Err("A varint is too long".to_string()),
+            read_varint(&mut overflowing)
+        );
+        let mut eleven_bytes: &[u8] = &[0x80; 11];
+        assert_eq!(
+            Err("A varint is too long".to_string()),
+            read_varint(&mut eleven_bytes)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_decode_residuals {
+    use super::*;
+
+    #[test]
+    fn test_run_past_the_last_pixel() {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 9 << 1 | 1);
+        assert_eq!(
+            Err("A run of pixels goes past the last pixel".to_string()),
+            decode_residuals(&bytes, 8)
+        );
+        assert_eq!(Ok(vec![0; 10]), decode_residuals(&bytes, 10));
+    }
+
+    #[test]
+    fn test_crafted_header() {
+        let mut bytes = CODEC_MAGIC.to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.push(32);
+        write_varint(&mut bytes, ((1 << 62) - 1) << 1 | 1);
+        assert_eq!(22, bytes.len());
+        assert!(decode_gray(&bytes).is_err());
+        // Largest order, but more pixels than allowed
+        bytes[4..12].copy_from_slice(&[0, 0, 1, 0, 0, 0, 1, 0]);
+        bytes[12] = 16;
+        assert_eq!(
+            Err("The image has too many pixels".to_string()),
+            decode_gray(&bytes)
+        );
+    }
+}
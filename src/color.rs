@@ -0,0 +1,193 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    pub const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Color as a `#rrggbb` string, usable in SVG and CSS.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Linear interpolation between `self` (t = 0) and `other` (t = 1).
+    pub fn lerp(&self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Colormap {
+    Grayscale,
+    Viridis,
+    Inferno,
+}
+
+const VIRIDIS: [Rgb; 9] = [
+    Rgb { r: 68, g: 1, b: 84 },
+    Rgb {
+        r: 71,
+        g: 44,
+        b: 122,
+    },
+    Rgb {
+        r: 59,
+        g: 81,
+        b: 139,
+    },
+    Rgb {
+        r: 44,
+        g: 113,
+        b: 142,
+    },
+    Rgb {
+        r: 33,
+        g: 144,
+        b: 141,
+    },
+    Rgb {
+        r: 39,
+        g: 173,
+        b: 129,
+    },
+    Rgb {
+        r: 92,
+        g: 200,
+        b: 99,
+    },
+    Rgb {
+        r: 170,
+        g: 220,
+        b: 50,
+    },
+    Rgb {
+        r: 253,
+        g: 231,
+        b: 37,
+    },
+];
+
+const INFERNO: [Rgb; 9] = [
+    Rgb { r: 0, g: 0, b: 4 },
+    Rgb {
+        r: 31,
+        g: 12,
+        b: 72,
+    },
+    Rgb {
+        r: 85,
+        g: 15,
+        b: 109,
+    },
+    Rgb {
+        r: 136,
+        g: 34,
+        b: 106,
+    },
+    Rgb {
+        r: 186,
+        g: 54,
+        b: 85,
+    },
+    Rgb {
+        r: 227,
+        g: 89,
+        b: 51,
+    },
+    Rgb {
+        r: 249,
+        g: 140,
+        b: 10,
+    },
+    Rgb {
+        r: 249,
+        g: 201,
+        b: 50,
+    },
+    Rgb {
+        r: 252,
+        g: 255,
+        b: 164,
+    },
+];
+
+impl Colormap {
+    /// Color of a normalized value, `t` is clamped into `[0, 1]`.
+    pub fn color(&self, t: f64) -> Rgb {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Colormap::Grayscale => Rgb::BLACK.lerp(Rgb::WHITE, t),
+            Colormap::Viridis => Colormap::sample(&VIRIDIS, t),
+            Colormap::Inferno => Colormap::sample(&INFERNO, t),
+        }
+    }
+
+    fn sample(anchors: &[Rgb], t: f64) -> Rgb {
+        let position = t * (anchors.len() - 1) as f64;
+        let lower = (position.floor() as usize).min(anchors.len() - 2);
+        anchors[lower].lerp(anchors[lower + 1], position - lower as f64)
+    }
+}
+
+#[cfg(test)]
+mod test_rgb {
+    use super::*;
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!("#ff0080", Rgb::new(255, 0, 128).to_hex());
+    }
+
+    #[test]
+    fn test_lerp_bounds_and_middle() {
+        let a = Rgb::new(0, 100, 200);
+        let b = Rgb::new(200, 100, 0);
+        assert_eq!(a, a.lerp(b, 0.0));
+        assert_eq!(b, a.lerp(b, 1.0));
+        assert_eq!(Rgb::new(100, 100, 100), a.lerp(b, 0.5));
+        assert_eq!(b, a.lerp(b, 3.0));
+    }
+}
+
+#[cfg(test)]
+mod test_colormap {
+    use super::*;
+
+    #[test]
+    fn test_grayscale_extremes() {
+        assert_eq!(Rgb::BLACK, Colormap::Grayscale.color(0.0));
+        assert_eq!(Rgb::WHITE, Colormap::Grayscale.color(1.0));
+        assert_eq!(Rgb::new(128, 128, 128), Colormap::Grayscale.color(0.5));
+    }
+
+    #[test]
+    fn test_viridis_anchors() {
+        assert_eq!(VIRIDIS[0], Colormap::Viridis.color(0.0));
+        assert_eq!(VIRIDIS[4], Colormap::Viridis.color(0.5));
+        assert_eq!(VIRIDIS[8], Colormap::Viridis.color(1.0));
+        assert_eq!(VIRIDIS[8], Colormap::Viridis.color(2.0));
+    }
+
+    #[test]
+    fn test_nan_is_lowest_color() {
+        assert_eq!(INFERNO[0], Colormap::Inferno.color(f64::NAN));
+    }
+}
@@ -0,0 +1,45 @@
+use crate::coordinate_value::CoordinateValue;
+
+/// Direction on the grid, `Up` going toward the row 0.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ];
+
+    /// Adjacent point in this direction, `None` when it is outside a grid of `number_of_rows`.
+    pub fn step(&self, point: CoordinateValue, number_of_rows: u32) -> Option<CoordinateValue> {
+        let next = match self {
+            Direction::Left => CoordinateValue {
+                x: point.x.checked_sub(1)?,
+                y: point.y,
+            },
+            Direction::Right => CoordinateValue {
+                x: point.x + 1,
+                y: point.y,
+            },
+            Direction::Up => CoordinateValue {
+                x: point.x,
+                y: point.y.checked_sub(1)?,
+            },
+            Direction::Down => CoordinateValue {
+                x: point.x,
+                y: point.y + 1,
+            },
+        };
+        if next.x >= number_of_rows || next.y >= number_of_rows {
+            return None;
+        }
+        Some(next)
+    }
+}
@@ -0,0 +1,178 @@
+//! Riemersma dithering: error diffusion along a curve filling the image.
+//!
+//! The pixels are visited along a `GeneralizedHilbertCurve`. The quantization errors of the last pixels
+//! are kept in a queue and added, weighted, to the next pixel. Following the curve instead of the rows
+//! spreads the error in every direction, without the diagonal patterns of Floyd-Steinberg.
+use crate::color::Rgb;
+use crate::generalized_curve::GeneralizedHilbertCurve;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DitherOptions {
+    /// Number of past errors carried along the curve.
+    pub queue_length: usize,
+    /// Weight of the oldest error divided by the weight of the newest one, between 0 excluded and 1.
+    pub decay: f64,
+}
+
+impl Default for DitherOptions {
+    fn default() -> Self {
+        Self {
+            queue_length: 16,
+            decay: 1.0 / 16.0,
+        }
+    }
+}
+
+/// Grayscale pixels, row-major, replaced by the palette values.
+pub fn dither_gray(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    palette: &[u8],
+    options: &DitherOptions,
+) -> Vec<u8> {
+    let palette_values: Vec<[f64; 1]> = palette.iter().map(|value| [f64::from(*value)]).collect();
+    let choices = diffuse(
+        width,
+        height,
+        |position| [f64::from(pixels[position])],
+        pixels.len(),
+        &palette_values,
+        options,
+    );
+    choices.into_iter().map(|choice| palette[choice]).collect()
+}
+
+/// RGB pixels, row-major, replaced by the palette colors. The closest color is found with the
+/// Euclidean distance on the channels.
+pub fn dither_rgb(
+    pixels: &[Rgb],
+    width: u32,
+    height: u32,
+    palette: &[Rgb],
+    options: &DitherOptions,
+) -> Vec<Rgb> {
+    let channels = |color: &Rgb| [f64::from(color.r), f64::from(color.g), f64::from(color.b)];
+    let palette_values: Vec<[f64; 3]> = palette.iter().map(channels).collect();
+    let choices = diffuse(
+        width,
+        height,
+        |position| channels(&pixels[position]),
+        pixels.len(),
+        &palette_values,
+        options,
+    );
+    choices.into_iter().map(|choice| palette[choice]).collect()
+}
+
+/// Palette position chosen for each pixel, row-major.
+fn diffuse<const C: usize, F: Fn(usize) -> [f64; C]>(
+    width: u32,
+    height: u32,
+    pixel: F,
+    pixel_count: usize,
+    palette: &[[f64; C]],
+    options: &DitherOptions,
+) -> Vec<usize> {
+    if pixel_count as u64 != u64::from(width) * u64::from(height) {
+        panic!("The number of pixels must be the width times the height");
+    }
+    if palette.is_empty() {
+        panic!("The palette must have at least one color");
+    }
+    let weights = error_weights(options);
+    let mut errors: VecDeque<[f64; C]> = VecDeque::from(vec![[0.0; C]; weights.len()]);
+    let mut choices = vec![0; pixel_count];
+    for point in GeneralizedHilbertCurve::new(width, height).iter() {
+        let position = point.y as usize * width as usize + point.x as usize;
+        let mut value = pixel(position);
+        for (error, weight) in errors.iter().zip(&weights) {
+            for channel in 0..C {
+                value[channel] += error[channel] * weight;
+            }
+        }
+        let choice = closest(palette, &value);
+        let mut error = value;
+        for (channel, error) in error.iter_mut().enumerate() {
+            *error -= palette[choice][channel];
+        }
+        errors.pop_front();
+        errors.push_back(error);
+        choices[position] = choice;
+    }
+    choices
+}
+
+/// Weights from the oldest error to the newest, growing geometrically and summing to 1 so every error
+/// is diffused once in total.
+fn error_weights(options: &DitherOptions) -> Vec<f64> {
+    if options.queue_length == 0 {
+        panic!("The queue length must be at least one");
+    }
+    if !(options.decay > 0.0 && options.decay <= 1.0) {
+        panic!("The decay must be above 0 and at most 1");
+    }
+    let steps = (options.queue_length - 1).max(1) as f64;
+    let weights: Vec<f64> = (0..options.queue_length)
+        .map(|age| {
+            options
+                .decay
+                .powf((options.queue_length - 1 - age) as f64 / steps)
+        })
+        .collect();
+    let total: f64 = weights.iter().sum();
+    weights.into_iter().map(|weight| weight / total).collect()
+}
+
+fn closest<const C: usize>(palette: &[[f64; C]], value: &[f64; C]) -> usize {
+    let distance = |color: &[f64; C]| -> f64 {
+        color
+            .iter()
+            .zip(value)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum()
+    };
+    let mut best = 0;
+    let mut best_distance = f64::INFINITY;
+    for (position, color) in palette.iter().enumerate() {
+        let candidate = distance(color);
+        if candidate < best_distance {
+            best = position;
+            best_distance = candidate;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test_error_weights {
+    use super::*;
+
+    #[test]
+    fn test_error_weights() {
+        let weights = error_weights(&DitherOptions::default());
+        assert_eq!(16, weights.len());
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((weights[0] / weights[15] - 1.0 / 16.0).abs() < 1e-12);
+        assert!(weights.windows(2).all(|pair| pair[0] < pair[1]));
+        let single = error_weights(&DitherOptions {
+            queue_length: 1,
+            decay: 0.5,
+        });
+        assert_eq!(vec![1.0], single);
+    }
+}
+
+#[cfg(test)]
+mod test_closest {
+    use super::*;
+
+    #[test]
+    fn test_closest() {
+        let palette = [[0.0], [128.0], [255.0]];
+        assert_eq!(0, closest(&palette, &[-40.0]));
+        assert_eq!(1, closest(&palette, &[100.0]));
+        assert_eq!(2, closest(&palette, &[300.0]));
+    }
+}
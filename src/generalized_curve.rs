@@ -0,0 +1,154 @@
+use crate::coordinate_value::CoordinateValue;
+
+/// Hilbert-like curve over a rectangle of any size (the "gilbert2d" construction).
+///
+/// Consecutive points are adjacent, except for one diagonal step on some rectangles having one odd side.
+/// The curve starts at (0, 0) and ends on the right side when the width is the largest dimension,
+/// on the bottom side otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GeneralizedHilbertCurve {
+    width: u32,
+    height: u32,
+}
+
+impl GeneralizedHilbertCurve {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of points of the curve.
+    pub fn len(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Points of the curve, from its start to its end.
+    pub fn iter(&self) -> GeneralizedHilbertIterator {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let mut stack = Vec::new();
+        if !self.is_empty() {
+            stack.push(if width >= height {
+                Block::Region([0, 0, width, 0, 0, height])
+            } else {
+                Block::Region([0, 0, 0, height, width, 0])
+            });
+        }
+        GeneralizedHilbertIterator {
+            stack,
+            remaining: self.len(),
+        }
+    }
+}
+
+/// Iterator over the points of a generalized curve, keeping one pending block per level of recursion.
+pub struct GeneralizedHilbertIterator {
+    stack: Vec<Block>,
+    remaining: u64,
+}
+
+enum Block {
+    /// Corner `x, y`, major axis `ax, ay` and minor axis `bx, by`.
+    Region([i64; 6]),
+    /// Next point, unit step and number of points left.
+    Line([i64; 4], i64),
+}
+
+impl Iterator for GeneralizedHilbertIterator {
+    type Item = CoordinateValue;
+
+    fn next(&mut self) -> Option<CoordinateValue> {
+        loop {
+            match self.stack.pop()? {
+                Block::Line([x, y, dx, dy], count) => {
+                    if count > 1 {
+                        self.stack
+                            .push(Block::Line([x + dx, y + dy, dx, dy], count - 1));
+                    }
+                    self.remaining -= 1;
+                    return Some(CoordinateValue {
+                        x: x as u32,
+                        y: y as u32,
+                    });
+                }
+                Block::Region(region) => self.split(region),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for GeneralizedHilbertIterator {}
+
+impl GeneralizedHilbertIterator {
+    /// Push the sub-blocks of a region in reverse order, so the first one is popped first.
+    fn split(&mut self, [x, y, ax, ay, bx, by]: [i64; 6]) {
+        let width = (ax + ay).abs();
+        let height = (bx + by).abs();
+        let (dax, day) = (ax.signum(), ay.signum());
+        let (dbx, dby) = (bx.signum(), by.signum());
+        if height == 1 {
+            self.stack.push(Block::Line([x, y, dax, day], width));
+            return;
+        }
+        if width == 1 {
+            self.stack.push(Block::Line([x, y, dbx, dby], height));
+            return;
+        }
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+        let width2 = (ax2 + ay2).abs();
+        let height2 = (bx2 + by2).abs();
+        if 2 * width > 3 * height {
+            // Long region: split in two along the major axis, keeping even halves when possible
+            if width2 % 2 == 1 && width > 2 {
+                ax2 += dax;
+                ay2 += day;
+            }
+            self.stack.push(Block::Region([
+                x + ax2,
+                y + ay2,
+                ax - ax2,
+                ay - ay2,
+                bx,
+                by,
+            ]));
+            self.stack.push(Block::Region([x, y, ax2, ay2, bx, by]));
+        } else {
+            if height2 % 2 == 1 && height > 2 {
+                bx2 += dbx;
+                by2 += dby;
+            }
+            self.stack.push(Block::Region([
+                x + (ax - dax) + (bx2 - dbx),
+                y + (ay - day) + (by2 - dby),
+                -bx2,
+                -by2,
+                -(ax - ax2),
+                -(ay - ay2),
+            ]));
+            self.stack.push(Block::Region([
+                x + bx2,
+                y + by2,
+                ax,
+                ay,
+                bx - bx2,
+                by - by2,
+            ]));
+            self.stack.push(Block::Region([x, y, bx2, by2, ax2, ay2]));
+        }
+    }
+}
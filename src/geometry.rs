@@ -0,0 +1,500 @@
+//! WKT and GeoJSON export of cells and regions, and WKT import of polygons.
+//!
+//! Coordinates are in grid units: the cell `(x, y)` is the square from `(x, y)` to `(x + 1, y + 1)`.
+//! Exterior rings have a positive signed area in these coordinates and holes a negative one.
+use crate::hilbert_cell::HilbertCell;
+use crate::hilbert_region::HilbertRegion;
+use crate::polygon::Polygon;
+use crate::rectangle::Rectangle;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+type Ring = Vec<(u64, u64)>;
+/// Intervals of the block sides on one grid line: blocks ending on the line, then blocks starting on it.
+type LineSides = (Vec<(u64, u64)>, Vec<(u64, u64)>);
+
+/// Closed ring of the square covered by a cell on the grid of `grid_order`.
+fn cell_ring(cell: &HilbertCell, grid_order: u16) -> Ring {
+    let bounds = cell.bounds(grid_order);
+    let (x_min, y_min) = (u64::from(bounds.x_min), u64::from(bounds.y_min));
+    let (x_max, y_max) = (u64::from(bounds.x_max) + 1, u64::from(bounds.y_max) + 1);
+    vec![
+        (x_min, y_min),
+        (x_max, y_min),
+        (x_max, y_max),
+        (x_min, y_max),
+        (x_min, y_min),
+    ]
+}
+
+pub fn cell_to_wkt(cell: &HilbertCell, grid_order: u16) -> String {
+    format!("POLYGON {}", wkt_rings(&[cell_ring(cell, grid_order)]))
+}
+
+/// Every cell as one polygon of a MULTIPOLYGON.
+pub fn cells_to_wkt(cells: &[HilbertCell], grid_order: u16) -> String {
+    let polygons: Vec<Vec<Ring>> = cells
+        .iter()
+        .map(|cell| vec![cell_ring(cell, grid_order)])
+        .collect();
+    wkt_multipolygon(&polygons)
+}
+
+/// Outline of the region, adjacent cells merged, as a MULTIPOLYGON.
+pub fn region_to_wkt(region: &HilbertRegion) -> String {
+    wkt_multipolygon(&region_polygons(region))
+}
+
+/// FeatureCollection with one Polygon feature per cell, with `order` and `index` properties.
+pub fn cells_to_geojson(cells: &[HilbertCell], grid_order: u16) -> String {
+    let features: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            geojson_feature(
+                "Polygon",
+                &geojson_rings(&[cell_ring(cell, grid_order)]),
+                &format!(r#""order":{},"index":{}"#, cell.order(), cell.index()),
+            )
+        })
+        .collect();
+    geojson_collection(&features)
+}
+
+/// FeatureCollection with one MultiPolygon feature for the region, with `order` and `area` properties.
+pub fn region_to_geojson(region: &HilbertRegion) -> String {
+    let polygons: Vec<String> = region_polygons(region)
+        .iter()
+        .map(|rings| geojson_rings(rings))
+        .collect();
+    let feature = geojson_feature(
+        "MultiPolygon",
+        &format!("[{}]", polygons.join(",")),
+        &format!(r#""order":{},"area":{}"#, region.order(), region.area()),
+    );
+    geojson_collection(&[feature])
+}
+
+fn wkt_rings(rings: &[Ring]) -> String {
+    let rings: Vec<String> = rings
+        .iter()
+        .map(|ring| {
+            let points: Vec<String> = ring.iter().map(|(x, y)| format!("{x} {y}")).collect();
+            format!("({})", points.join(", "))
+        })
+        .collect();
+    format!("({})", rings.join(", "))
+}
+
+fn wkt_multipolygon(polygons: &[Vec<Ring>]) -> String {
+    if polygons.is_empty() {
+        return "MULTIPOLYGON EMPTY".to_string();
+    }
+    let polygons: Vec<String> = polygons.iter().map(|rings| wkt_rings(rings)).collect();
+    format!("MULTIPOLYGON ({})", polygons.join(", "))
+}
+
+fn geojson_rings(rings: &[Ring]) -> String {
+    let rings: Vec<String> = rings
+        .iter()
+        .map(|ring| {
+            let points: Vec<String> = ring.iter().map(|(x, y)| format!("[{x},{y}]")).collect();
+            format!("[{}]", points.join(","))
+        })
+        .collect();
+    format!("[{}]", rings.join(","))
+}
+
+fn geojson_feature(geometry_type: &str, coordinates: &str, properties: &str) -> String {
+    format!(
+        r#"{{"type":"Feature","geometry":{{"type":"{geometry_type}","coordinates":{coordinates}}},"properties":{{{properties}}}}}"#
+    )
+}
+
+fn geojson_collection(features: &[String]) -> String {
+    let mut json = String::from(r#"{"type":"FeatureCollection","features":["#);
+    let _ = write!(json, "{}", features.join(","));
+    json.push_str("]}");
+    json
+}
+
+/// Polygons of the outline of the region, each one being an exterior ring followed by its holes.
+///
+/// The boundary is found line by line: on each grid line, the sides of the blocks above and below cancel
+/// where they overlap. The remaining edges are oriented with the region on their right and chained into rings.
+fn region_polygons(region: &HilbertRegion) -> Vec<Vec<Ring>> {
+    let blocks: Vec<Rectangle> = region
+        .blocks()
+        .map(|(start, level)| region.hilbert_curve().block_rectangle(start, level))
+        .collect();
+    let mut rows: BTreeMap<u64, LineSides> = BTreeMap::new();
+    let mut columns: BTreeMap<u64, LineSides> = BTreeMap::new();
+    for block in &blocks {
+        let (x_min, y_min) = (u64::from(block.x_min), u64::from(block.y_min));
+        let (x_max, y_max) = (u64::from(block.x_max) + 1, u64::from(block.y_max) + 1);
+        rows.entry(y_max).or_default().0.push((x_min, x_max));
+        rows.entry(y_min).or_default().1.push((x_min, x_max));
+        columns.entry(x_max).or_default().0.push((y_min, y_max));
+        columns.entry(x_min).or_default().1.push((y_min, y_max));
+    }
+    let mut edges: Vec<((u64, u64), (u64, u64))> = Vec::new();
+    for (y, (above, below)) in rows {
+        let (above, below) = (merge_intervals(above), merge_intervals(below));
+        for (from, to) in subtract_intervals(&below, &above) {
+            edges.push(((from, y), (to, y)));
+        }
+        for (from, to) in subtract_intervals(&above, &below) {
+            edges.push(((to, y), (from, y)));
+        }
+    }
+    for (x, (left, right)) in columns {
+        let (left, right) = (merge_intervals(left), merge_intervals(right));
+        for (from, to) in subtract_intervals(&left, &right) {
+            edges.push(((x, from), (x, to)));
+        }
+        for (from, to) in subtract_intervals(&right, &left) {
+            edges.push(((x, to), (x, from)));
+        }
+    }
+    let rings = chain_rings(edges);
+    let (exteriors, holes): (Vec<Ring>, Vec<Ring>) =
+        rings.into_iter().partition(|ring| signed_area(ring) > 0);
+    let mut polygons: Vec<Vec<Ring>> = exteriors.into_iter().map(|ring| vec![ring]).collect();
+    for hole in holes {
+        // The cell on the right of the first edge of a hole belongs to the region around the hole
+        let ((x1, y1), (x2, y2)) = (hole[0], hole[1]);
+        let (dx, dy) = (
+            (x2 as f64 - x1 as f64).signum(),
+            (y2 as f64 - y1 as f64).signum(),
+        );
+        let x = (x1 as f64 + x2 as f64) / 2.0 - dy * 0.5;
+        let y = (y1 as f64 + y2 as f64) / 2.0 + dx * 0.5;
+        let owner = polygons
+            .iter_mut()
+            .filter(|rings| ring_contains(&rings[0], x, y))
+            .min_by_key(|rings| signed_area(&rings[0]));
+        if let Some(rings) = owner {
+            rings.push(hole);
+        }
+    }
+    polygons
+}
+
+fn merge_intervals(mut intervals: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Parts of the sorted, disjoint `intervals` that are not in the sorted, disjoint `removed`.
+fn subtract_intervals(intervals: &[(u64, u64)], removed: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut result = Vec::new();
+    let mut next_removed = 0;
+    for (start, end) in intervals {
+        let mut start = *start;
+        while next_removed < removed.len() && removed[next_removed].1 <= start {
+            next_removed += 1;
+        }
+        let mut position = next_removed;
+        while position < removed.len() && removed[position].0 < *end {
+            if removed[position].0 > start {
+                result.push((start, removed[position].0));
+            }
+            start = start.max(removed[position].1);
+            position += 1;
+        }
+        if start < *end {
+            result.push((start, *end));
+        }
+    }
+    result
+}
+
+/// Chain oriented edges into closed rings. Where two rings touch at a corner, the ring turns right so
+/// that it stays around the same cells.
+fn chain_rings(edges: Vec<((u64, u64), (u64, u64))>) -> Vec<Ring> {
+    let mut outgoing: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for (position, (from, _)) in edges.iter().enumerate() {
+        outgoing.entry(*from).or_default().push(position);
+    }
+    let direction = |(from, to): ((u64, u64), (u64, u64))| {
+        (
+            (to.0 as i64 - from.0 as i64).signum(),
+            (to.1 as i64 - from.1 as i64).signum(),
+        )
+    };
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = vec![edges[first].0];
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let (_, to) = edges[current];
+            ring.push(to);
+            if to == edges[first].0 {
+                break;
+            }
+            let (dx, dy) = direction(edges[current]);
+            let candidates = &outgoing[&to];
+            let turn_rank = |edge: usize| {
+                let next = direction(edges[edge]);
+                if next == (-dy, dx) {
+                    0 // Right turn, y growing downward
+                } else if next == (dx, dy) {
+                    1
+                } else {
+                    2
+                }
+            };
+            current = *candidates
+                .iter()
+                .filter(|edge| !used[**edge])
+                .min_by_key(|edge| turn_rank(**edge))
+                .unwrap();
+        }
+        rings.push(ring);
+    }
+    rings
+}
+
+/// Twice the signed area of a closed ring.
+fn signed_area(ring: &Ring) -> i128 {
+    ring.windows(2)
+        .map(|pair| {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            i128::from(x1) * i128::from(y2) - i128::from(x2) * i128::from(y1)
+        })
+        .sum()
+}
+
+fn ring_contains(ring: &Ring, x: f64, y: f64) -> bool {
+    let mut inside = false;
+    for pair in ring.windows(2) {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        let (x1, y1, x2, y2) = (x1 as f64, y1 as f64, x2 as f64, y2 as f64);
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Polygons of a WKT `POLYGON` or `MULTIPOLYGON`, in grid units. Z and M values are ignored.
+pub fn parse_wkt_polygons(wkt: &str) -> Result<Vec<Polygon>, String> {
+    let tokens = tokenize(wkt)?;
+    let mut parser = WktParser {
+        tokens: &tokens,
+        position: 0,
+    };
+    let keyword = parser.keyword()?;
+    let polygons = match keyword.as_str() {
+        "POLYGON" => {
+            parser.skip_dimension();
+            if parser.empty() {
+                Vec::new()
+            } else {
+                vec![parser.polygon()?]
+            }
+        }
+        "MULTIPOLYGON" => {
+            parser.skip_dimension();
+            let mut polygons = Vec::new();
+            if !parser.empty() {
+                parser.expect(&Token::Open)?;
+                loop {
+                    polygons.push(parser.polygon()?);
+                    if !parser.next_is(&Token::Comma) {
+                        break;
+                    }
+                }
+                parser.expect(&Token::Close)?;
+            }
+            polygons
+        }
+        _ => {
+            return Err(format!(
+                "The geometry {keyword} is not a POLYGON or a MULTIPOLYGON"
+            ))
+        }
+    };
+    if parser.position != tokens.len() {
+        return Err("There is unexpected text after the geometry".to_string());
+    }
+    Ok(polygons)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(wkt: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut characters = wkt.char_indices().peekable();
+    while let Some((start, character)) = characters.next() {
+        match character {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            character if character.is_whitespace() => {}
+            character if character.is_ascii_alphabetic() => {
+                let mut end = start + 1;
+                while let Some((position, next)) = characters.peek() {
+                    if !next.is_ascii_alphabetic() {
+                        break;
+                    }
+                    end = position + 1;
+                    characters.next();
+                }
+                tokens.push(Token::Word(wkt[start..end].to_ascii_uppercase()));
+            }
+            _ => {
+                let mut end = start + character.len_utf8();
+                while let Some((position, next)) = characters.peek() {
+                    if next.is_whitespace() || matches!(next, '(' | ')' | ',') {
+                        break;
+                    }
+                    end = position + next.len_utf8();
+                    characters.next();
+                }
+                let number = wkt[start..end]
+                    .parse::<f64>()
+                    .map_err(|_| format!("The value {} is not a number", &wkt[start..end]))?;
+                tokens.push(Token::Number(number));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct WktParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl WktParser<'_> {
+    fn keyword(&mut self) -> Result<String, String> {
+        match self.tokens.get(self.position) {
+            Some(Token::Word(word)) => {
+                self.position += 1;
+                Ok(word.clone())
+            }
+            _ => Err("The text must start with a geometry type".to_string()),
+        }
+    }
+
+    fn skip_dimension(&mut self) {
+        if let Some(Token::Word(word)) = self.tokens.get(self.position) {
+            if matches!(word.as_str(), "Z" | "M" | "ZM") {
+                self.position += 1;
+            }
+        }
+    }
+
+    fn empty(&mut self) -> bool {
+        self.next_is(&Token::Word("EMPTY".to_string()))
+    }
+
+    fn next_is(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.next_is(token) {
+            return Ok(());
+        }
+        Err(format!("Expected {token:?} at token {}", self.position))
+    }
+
+    fn polygon(&mut self) -> Result<Polygon, String> {
+        self.expect(&Token::Open)?;
+        let mut rings = vec![self.ring()?];
+        while self.next_is(&Token::Comma) {
+            rings.push(self.ring()?);
+        }
+        self.expect(&Token::Close)?;
+        let exterior = rings.remove(0);
+        Ok(Polygon::with_holes(exterior, rings))
+    }
+
+    fn ring(&mut self) -> Result<Vec<(f64, f64)>, String> {
+        self.expect(&Token::Open)?;
+        let mut ring = Vec::new();
+        loop {
+            let mut values = Vec::new();
+            while let Some(Token::Number(value)) = self.tokens.get(self.position) {
+                values.push(*value);
+                self.position += 1;
+            }
+            if !(2..=4).contains(&values.len()) || values.iter().any(|value| !value.is_finite()) {
+                return Err("A point must have 2 to 4 finite coordinates".to_string());
+            }
+            ring.push((values[0], values[1]));
+            if !self.next_is(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect(&Token::Close)?;
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            return Err("A ring must have at least 3 vertices".to_string());
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(test)]
+mod test_subtract_intervals {
+    use super::*;
+
+    #[test]
+    fn test_subtract_intervals() {
+        assert_eq!(
+            vec![(0, 2), (5, 6), (9, 10)],
+            subtract_intervals(&[(0, 6), (8, 10)], &[(2, 5), (6, 9)])
+        );
+        assert_eq!(vec![(0, 4)], subtract_intervals(&[(0, 4)], &[]));
+        assert!(subtract_intervals(&[(1, 3)], &[(0, 8)]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_region_polygons {
+    use super::*;
+
+    #[test]
+    fn test_region_polygons_hole_and_corner() {
+        // A ring of cells around a hole, and a cell touching it by a corner only
+        let outer = HilbertRegion::from_rectangle(3, &Rectangle::new(0, 0, 3, 3));
+        let hole = HilbertRegion::from_rectangle(3, &Rectangle::new(1, 1, 2, 2));
+        let corner = HilbertRegion::from_rectangle(3, &Rectangle::new(4, 4, 4, 4));
+        let region = outer.difference(&hole).union(&corner);
+        let polygons = region_polygons(&region);
+        assert_eq!(2, polygons.len());
+        let mut areas: Vec<Vec<i128>> = polygons
+            .iter()
+            .map(|rings| rings.iter().map(signed_area).collect())
+            .collect();
+        areas.sort();
+        assert_eq!(vec![vec![2], vec![32, -8]], areas);
+    }
+}
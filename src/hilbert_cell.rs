@@ -0,0 +1,129 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+pub const MAXIMUM_CELL_ORDER: u16 = 16;
+
+/// Cell of the curve at a given order: the quadrant holding the indices `index * 4^k .. (index + 1) * 4^k`
+/// of a curve `k` orders finer.
+///
+/// Cells are ordered by their first index at the finest order, a parent coming before its first child.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HilbertCell {
+    order: u16,
+    index: u32,
+}
+
+impl HilbertCell {
+    pub fn new(order: u16, index: u32) -> Self {
+        if order > MAXIMUM_CELL_ORDER {
+            panic!("The order of a cell must be 16 or less");
+        }
+        if u64::from(index) >= u64::pow(4, order.into()) {
+            panic!("The index is above the supported amount of space the current order support. Reduce the index or increase the order.");
+        }
+        Self { order, index }
+    }
+
+    /// Cell covering the whole curve.
+    pub fn root() -> Self {
+        Self { order: 0, index: 0 }
+    }
+
+    /// Cell of order `cell_order` containing a point of the `point_order` curve.
+    pub fn from_point(point: CoordinateValue, point_order: u16, cell_order: u16) -> Self {
+        if cell_order > point_order {
+            panic!("The cell order must be lower or equal to the point order");
+        }
+        let index = HilbertCurveAlgorithm::new(point_order).point_to_index(point);
+        Self::new(cell_order, shift_down(index, point_order - cell_order))
+    }
+
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn parent(&self) -> Option<HilbertCell> {
+        if self.order == 0 {
+            return None;
+        }
+        Some(Self {
+            order: self.order - 1,
+            index: self.index >> 2,
+        })
+    }
+
+    /// The four children, in curve order.
+    pub fn children(&self) -> [HilbertCell; 4] {
+        if self.order == MAXIMUM_CELL_ORDER {
+            panic!("A cell of order 16 cannot be split");
+        }
+        let first = self.index << 2;
+        [0, 1, 2, 3].map(|child| Self {
+            order: self.order + 1,
+            index: first + child,
+        })
+    }
+
+    /// True when `other` is this cell or one of its descendants.
+    pub fn contains(&self, other: &HilbertCell) -> bool {
+        other.order >= self.order && shift_down(other.index, other.order - self.order) == self.index
+    }
+
+    /// Indices of the `target_order` curve inside the cell.
+    pub fn index_range(&self, target_order: u16) -> RangeInclusive<u32> {
+        if target_order < self.order || target_order > MAXIMUM_CELL_ORDER {
+            panic!("The target order must be between the cell order and 16");
+        }
+        let shift = 2 * u32::from(target_order - self.order);
+        let start = u64::from(self.index) << shift;
+        let end = start + (1u64 << shift) - 1;
+        start as u32..=end as u32
+    }
+
+    /// Square covered by the cell on the grid of the `target_order` curve.
+    pub fn bounds(&self, target_order: u16) -> Rectangle {
+        let start = *self.index_range(target_order).start();
+        HilbertCurveAlgorithm::new(target_order).block_rectangle(start, target_order - self.order)
+    }
+
+    fn finest_start(&self) -> u64 {
+        u64::from(self.index) << (2 * u32::from(MAXIMUM_CELL_ORDER - self.order))
+    }
+}
+
+impl Ord for HilbertCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.finest_start()
+            .cmp(&other.finest_start())
+            .then(self.order.cmp(&other.order))
+    }
+}
+
+impl PartialOrd for HilbertCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn shift_down(index: u32, orders: u16) -> u32 {
+    (u64::from(index) >> (2 * u32::from(orders))) as u32
+}
+
+#[cfg(test)]
+mod test_shift_down {
+    use super::*;
+
+    #[test]
+    fn test_shift_down() {
+        assert_eq!(0b1011, shift_down(0b101101, 1));
+        assert_eq!(0, shift_down(u32::MAX, 16));
+        assert_eq!(3, shift_down(u32::MAX, 15));
+    }
+}
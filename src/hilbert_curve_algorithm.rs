@@ -1,6 +1,13 @@
+use crate::circle::Circle;
 use crate::coordinate_value::CoordinateValue;
+use crate::direction::Direction;
+use crate::polygon::Polygon;
+use crate::rectangle::Rectangle;
+use crate::region_coverer::{BlockRelation, CoverableRegion};
 use std::mem;
+use std::ops::RangeInclusive;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct HilbertCurveAlgorithm {
     order: u16,
 }
@@ -10,10 +17,18 @@ impl HilbertCurveAlgorithm {
         Self { order }
     }
 
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    pub fn number_of_rows(&self) -> u32 {
+        u32::pow(2, self.order.into())
+    }
+
     pub fn index_to_point(&self, index: u32) -> CoordinateValue {
         let number_row = u32::pow(2, self.order.into());
-        let maximum_data_size = u32::pow(number_row, 2);
-        if index >= maximum_data_size {
+        let maximum_data_size = u64::pow(number_row.into(), 2); // 2^32 at order 16 does not fit in u32
+        if u64::from(index) >= maximum_data_size {
             panic!("The index is above the supported amount of space the current order support. Reduce the index or increase the order.");
         }
 
@@ -64,7 +79,7 @@ impl HilbertCurveAlgorithm {
         let and_op: u32 = 1 & asd;
         and_op
     }
-    fn rotate_point(mut point: &mut CoordinateValue, rx: u32, ry: u32, number_columns: u32) {
+    fn rotate_point(point: &mut CoordinateValue, rx: u32, ry: u32, number_columns: u32) {
         if ry == 0 {
             if rx == 1 {
                 point.x = number_columns - 1 - point.x;
@@ -73,7 +88,7 @@ impl HilbertCurveAlgorithm {
             mem::swap(&mut point.x, &mut point.y);
         }
     }
-    fn move_point(mut point: &mut CoordinateValue, rx: u32, ry: u32, order_index: u32) {
+    fn move_point(point: &mut CoordinateValue, rx: u32, ry: u32, order_index: u32) {
         point.x += order_index * rx;
         point.y += order_index * ry;
     }
@@ -108,8 +123,241 @@ impl HilbertCurveAlgorithm {
             y: point.y / len,
         }
     }
+
+    /// Sorted and merged index ranges covering exactly the cells of the rectangle.
+    ///
+    /// The curve is split in quadrants recursively: a quadrant inside the rectangle becomes one range,
+    /// a quadrant outside is skipped and the others are split again.
+    pub fn ranges_for_rectangle(&self, rectangle: &Rectangle) -> Vec<RangeInclusive<u32>> {
+        self.ranges_for_region(rectangle)
+    }
+
+    /// Sorted and merged index ranges of the cells overlapping the polygon.
+    ///
+    /// With `max_ranges`, the ranges separated by the smallest gaps are merged until there are at most
+    /// `max_ranges` of them, trading extra cells for fewer scans.
+    pub fn ranges_for_polygon(
+        &self,
+        polygon: &Polygon,
+        max_ranges: Option<usize>,
+    ) -> Vec<RangeInclusive<u32>> {
+        coarsen_ranges(self.ranges_for_region(polygon), max_ranges)
+    }
+
+    /// Sorted and merged index ranges of the cells overlapping the circle, see `ranges_for_polygon`.
+    pub fn ranges_for_circle(
+        &self,
+        center: (f64, f64),
+        radius: f64,
+        max_ranges: Option<usize>,
+    ) -> Vec<RangeInclusive<u32>> {
+        coarsen_ranges(
+            self.ranges_for_region(&Circle::new(center, radius)),
+            max_ranges,
+        )
+    }
+
+    /// Quadrant descent keeping the quadrants inside the region and the single cells partially inside.
+    fn ranges_for_region<R: CoverableRegion>(&self, region: &R) -> Vec<RangeInclusive<u32>> {
+        let mut ranges: Vec<RangeInclusive<u32>> = Vec::new();
+        let mut stack = vec![(0u32, self.order)];
+        while let Some((start, level)) = stack.pop() {
+            let relation = region.relation(&self.block_rectangle(start, level));
+            if relation == BlockRelation::Outside {
+                continue;
+            }
+            if level == 0 || relation == BlockRelation::Inside {
+                let end = start + (u64::pow(4, level.into()) - 1) as u32;
+                match ranges.last_mut() {
+                    Some(last) if u64::from(*last.end()) + 1 == u64::from(start) => {
+                        *last = *last.start()..=end
+                    }
+                    _ => ranges.push(start..=end),
+                }
+                continue;
+            }
+            let child_size = u32::pow(4, (level - 1).into());
+            for child in (0..4).rev() {
+                stack.push((start + child * child_size, level - 1));
+            }
+        }
+        ranges
+    }
+
+    /// Square covered by the `4^level` consecutive indices starting at `start`, which must be a multiple of `4^level`.
+    pub(crate) fn block_rectangle(&self, start: u32, level: u16) -> Rectangle {
+        let side = u32::pow(2, level.into());
+        let point = self.index_to_point(start);
+        let x_min = point.x & !(side - 1);
+        let y_min = point.y & !(side - 1);
+        Rectangle::new(x_min, y_min, x_min + (side - 1), y_min + (side - 1))
+    }
+
+    /// Rectangles covering exactly the cells of an index range, in curve order.
+    ///
+    /// The range is split in aligned quadrants, and consecutive quadrants sharing a whole side are merged.
+    pub fn range_to_rectangles(&self, range: &RangeInclusive<u32>) -> Vec<Rectangle> {
+        let mut rectangles: Vec<Rectangle> = Vec::new();
+        for (start, level) in self.range_blocks(range) {
+            let block = self.block_rectangle(start, level);
+            match rectangles.last_mut() {
+                Some(last) if shares_side(last, &block) => *last = last.union(&block),
+                _ => rectangles.push(block),
+            }
+        }
+        rectangles
+    }
+
+    /// Largest aligned blocks `(start, level)` covering an index range, each one holding `4^level` indices.
+    pub(crate) fn range_blocks(&self, range: &RangeInclusive<u32>) -> Vec<(u32, u16)> {
+        let mut blocks = Vec::new();
+        let mut start = u64::from(*range.start());
+        let end = u64::from(*range.end()) + 1;
+        while start < end {
+            let mut level = 0;
+            while level < self.order
+                && start.is_multiple_of(1 << (2 * (level + 1)))
+                && start + (1 << (2 * (level + 1))) <= end
+            {
+                level += 1;
+            }
+            blocks.push((start as u32, level));
+            start += 1 << (2 * level);
+        }
+        blocks
+    }
+
+    /// Points of the curve, from the index 0 to the last index.
+    pub fn iter(&self) -> HilbertCurveIterator {
+        let number_of_rows = u64::from(self.number_of_rows());
+        HilbertCurveIterator {
+            hilbert_curve: *self,
+            next_index: 0,
+            end_index: number_of_rows * number_of_rows,
+        }
+    }
+
+    /// Adjacent point and its index, given a point and its index.
+    ///
+    /// Only the lowest levels where the point and its neighbor differ are computed again. The
+    /// orientation of these levels comes from the digits of `index` above them.
+    pub fn neighbor(
+        &self,
+        point: CoordinateValue,
+        index: u32,
+        direction: Direction,
+    ) -> Option<(CoordinateValue, u32)> {
+        let next = direction.step(point, self.number_of_rows())?;
+        let different_bits = (point.x ^ next.x) | (point.y ^ next.y);
+        let levels = u32::BITS - different_bits.leading_zeros(); // Number of levels to compute again
+        let mut swap = false;
+        let mut complement = false;
+        for level in levels..u32::from(self.order) {
+            let digit = (index >> (2 * level)) & 3;
+            swap ^= digit == 0 || digit == 3;
+            complement ^= digit == 3;
+        }
+        let mask = ((1u64 << levels) - 1) as u32;
+        let mut low_point = CoordinateValue {
+            x: next.x & mask,
+            y: next.y & mask,
+        };
+        if complement {
+            low_point.x ^= mask;
+            low_point.y ^= mask;
+        }
+        if swap {
+            mem::swap(&mut low_point.x, &mut low_point.y);
+        }
+        let mut rx: u32 = 0;
+        let mut ry: u32 = 0;
+        let mut low_index: u32 = 0;
+        let mut row_index = 1 << (levels - 1);
+        while row_index > 0 {
+            HilbertCurveAlgorithm::update_rx_from_point(&mut rx, low_point, row_index);
+            HilbertCurveAlgorithm::update_ry_from_point(&mut ry, low_point, row_index);
+            low_index += HilbertCurveAlgorithm::get_new_index_from_rows(row_index, rx, ry);
+            HilbertCurveAlgorithm::rotate_point(&mut low_point, rx, ry, mask + 1);
+            row_index /= 2;
+        }
+        let high_mask = !((1u64 << (2 * levels)) - 1) as u32;
+        Some((next, (index & high_mask) | low_index))
+    }
+}
+
+/// True when the rectangles are side by side and their union is a rectangle.
+fn shares_side(a: &Rectangle, b: &Rectangle) -> bool {
+    let touch_x = u64::from(a.x_max) + 1 == u64::from(b.x_min)
+        || u64::from(b.x_max) + 1 == u64::from(a.x_min);
+    let touch_y = u64::from(a.y_max) + 1 == u64::from(b.y_min)
+        || u64::from(b.y_max) + 1 == u64::from(a.y_min);
+    (touch_x && a.y_min == b.y_min && a.y_max == b.y_max)
+        || (touch_y && a.x_min == b.x_min && a.x_max == b.x_max)
+}
+
+/// Merge the ranges separated by the smallest gaps until at most `max_ranges` are left.
+pub(crate) fn coarsen_ranges(
+    ranges: Vec<RangeInclusive<u32>>,
+    max_ranges: Option<usize>,
+) -> Vec<RangeInclusive<u32>> {
+    let Some(max_ranges) = max_ranges else {
+        return ranges;
+    };
+    if max_ranges == 0 {
+        panic!("The maximum number of ranges must be at least one");
+    }
+    if ranges.len() <= max_ranges {
+        return ranges;
+    }
+    // Gap after each range but the last, the widest ones are kept
+    let mut gaps: Vec<(u32, usize)> = ranges
+        .windows(2)
+        .enumerate()
+        .map(|(position, pair)| (*pair[1].start() - *pair[0].end(), position))
+        .collect();
+    gaps.sort_unstable_by(|a, b| b.cmp(a));
+    let mut kept: Vec<usize> = gaps[..max_ranges - 1]
+        .iter()
+        .map(|(_, position)| *position)
+        .collect();
+    kept.sort_unstable();
+    let mut coarse = Vec::with_capacity(max_ranges);
+    let mut start = *ranges[0].start();
+    for position in kept {
+        coarse.push(start..=*ranges[position].end());
+        start = *ranges[position + 1].start();
+    }
+    coarse.push(start..=*ranges[ranges.len() - 1].end());
+    coarse
+}
+
+/// Iterator over the points of a curve in index order.
+pub struct HilbertCurveIterator {
+    hilbert_curve: HilbertCurveAlgorithm,
+    next_index: u64,
+    end_index: u64,
+}
+
+impl Iterator for HilbertCurveIterator {
+    type Item = CoordinateValue;
+
+    fn next(&mut self) -> Option<CoordinateValue> {
+        if self.next_index >= self.end_index {
+            return None;
+        }
+        let point = self.hilbert_curve.index_to_point(self.next_index as u32);
+        self.next_index += 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_index - self.next_index) as usize;
+        (remaining, Some(remaining))
+    }
 }
 
+impl ExactSizeIterator for HilbertCurveIterator {}
+
 #[cfg(test)]
 mod test_get_rx {
     use super::*;
@@ -509,3 +757,54 @@ mod test_deoffset_point {
         assert_eq!(3, result.y, "Y value is wrong");
     }
 }
+
+#[cfg(test)]
+mod test_block_rectangle {
+    use super::*;
+
+    #[test]
+    fn test_block_rectangle_second_order() {
+        let hilbert_curve = HilbertCurveAlgorithm::new(2);
+        assert_eq!(Rectangle::new(0, 0, 1, 1), hilbert_curve.block_rectangle(0, 1));
+        assert_eq!(Rectangle::new(0, 2, 1, 3), hilbert_curve.block_rectangle(4, 1));
+        assert_eq!(Rectangle::new(2, 0, 3, 1), hilbert_curve.block_rectangle(12, 1));
+        assert_eq!(Rectangle::new(0, 0, 3, 3), hilbert_curve.block_rectangle(0, 2));
+        assert_eq!(Rectangle::new(1, 2, 1, 2), hilbert_curve.block_rectangle(7, 0));
+    }
+}
+
+#[cfg(test)]
+mod test_range_blocks {
+    use super::*;
+
+    #[test]
+    fn test_range_blocks() {
+        let hilbert_curve = HilbertCurveAlgorithm::new(2);
+        assert_eq!(vec![(0, 2)], hilbert_curve.range_blocks(&(0..=15)));
+        assert_eq!(
+            vec![(3, 0), (4, 1), (8, 0), (9, 0)],
+            hilbert_curve.range_blocks(&(3..=9))
+        );
+        assert_eq!(
+            vec![(0, 16)],
+            HilbertCurveAlgorithm::new(16).range_blocks(&(0..=u32::MAX))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_coarsen_ranges {
+    use super::*;
+
+    #[test]
+    fn test_coarsen_ranges() {
+        let ranges = vec![0..=1, 3..=4, 10..=10, 12..=20, 40..=41];
+        assert_eq!(ranges, coarsen_ranges(ranges.clone(), None));
+        assert_eq!(ranges, coarsen_ranges(ranges.clone(), Some(5)));
+        assert_eq!(
+            vec![0..=4, 10..=20, 40..=41],
+            coarsen_ranges(ranges.clone(), Some(3))
+        );
+        assert_eq!(vec![0..=41], coarsen_ranges(ranges, Some(1)));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::direction::Direction;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use std::ops::{Index, IndexMut};
+
+/// Square grid whose values are stored in curve order: the value of a point is at its Hilbert index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HilbertGrid<T> {
+    hilbert_curve: HilbertCurveAlgorithm,
+    values: Vec<T>,
+}
+
+impl<T> HilbertGrid<T> {
+    pub fn new(order: u16, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        if order > 15 {
+            panic!("The order of a grid must be 15 or less");
+        }
+        let number_of_rows = 1usize << order;
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(order),
+            values: vec![fill; number_of_rows * number_of_rows],
+        }
+    }
+
+    /// Grid from values stored row by row, the row 0 first.
+    pub fn from_row_major(order: u16, values: Vec<T>) -> Self {
+        if order > 15 {
+            panic!("The order of a grid must be 15 or less");
+        }
+        let hilbert_curve = HilbertCurveAlgorithm::new(order);
+        let number_of_rows = hilbert_curve.number_of_rows() as usize;
+        if values.len() != number_of_rows * number_of_rows {
+            panic!("The number of values must be the number of cells of the grid");
+        }
+        let mut slots: Vec<Option<T>> = values.into_iter().map(Some).collect();
+        let values = hilbert_curve
+            .iter()
+            .map(|point| {
+                slots[point.y as usize * number_of_rows + point.x as usize]
+                    .take()
+                    .unwrap()
+            })
+            .collect();
+        Self {
+            hilbert_curve,
+            values,
+        }
+    }
+
+    /// Values row by row, the row 0 first.
+    pub fn to_row_major(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter_row_major()
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    pub fn hilbert_curve(&self) -> &HilbertCurveAlgorithm {
+        &self.hilbert_curve
+    }
+
+    pub fn order(&self) -> u16 {
+        self.hilbert_curve.order()
+    }
+
+    pub fn number_of_rows(&self) -> u32 {
+        self.hilbert_curve.number_of_rows()
+    }
+
+    /// Values in curve order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+
+    pub fn get(&self, point: CoordinateValue) -> Option<&T> {
+        if !self.is_inside(point) {
+            return None;
+        }
+        self.values
+            .get(self.hilbert_curve.point_to_index(point) as usize)
+    }
+
+    pub fn get_mut(&mut self, point: CoordinateValue) -> Option<&mut T> {
+        if !self.is_inside(point) {
+            return None;
+        }
+        let index = self.hilbert_curve.point_to_index(point) as usize;
+        self.values.get_mut(index)
+    }
+
+    /// Points with their value, in curve order, i.e. in memory order.
+    pub fn iter(&self) -> impl Iterator<Item = (CoordinateValue, &T)> + '_ {
+        self.hilbert_curve.iter().zip(self.values.iter())
+    }
+
+    /// Points with their value, row by row.
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (CoordinateValue, &T)> + '_ {
+        let number_of_rows = self.number_of_rows();
+        (0..number_of_rows).flat_map(move |y| {
+            let first = CoordinateValue { x: 0, y };
+            let index = self.hilbert_curve.point_to_index(first);
+            // Each step right only computes again the levels where the point changes
+            std::iter::successors(Some((first, index)), move |(point, index)| {
+                self.hilbert_curve
+                    .neighbor(*point, *index, Direction::Right)
+            })
+            .map(move |(point, index)| (point, &self.values[index as usize]))
+        })
+    }
+
+    /// Adjacent point, its Hilbert index and its value, from a point and its Hilbert index.
+    pub fn neighbor(
+        &self,
+        point: CoordinateValue,
+        index: u32,
+        direction: Direction,
+    ) -> Option<(CoordinateValue, u32, &T)> {
+        let (next, next_index) = self.hilbert_curve.neighbor(point, index, direction)?;
+        Some((next, next_index, &self.values[next_index as usize]))
+    }
+
+    fn is_inside(&self, point: CoordinateValue) -> bool {
+        let number_of_rows = self.number_of_rows();
+        point.x < number_of_rows && point.y < number_of_rows
+    }
+}
+
+impl<T> Index<CoordinateValue> for HilbertGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: CoordinateValue) -> &T {
+        &self.values[self.hilbert_curve.point_to_index(point) as usize]
+    }
+}
+
+impl<T> IndexMut<CoordinateValue> for HilbertGrid<T> {
+    fn index_mut(&mut self, point: CoordinateValue) -> &mut T {
+        let index = self.hilbert_curve.point_to_index(point) as usize;
+        &mut self.values[index]
+    }
+}
@@ -0,0 +1,138 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::hilbert_rtree::squared_distance;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Blocks holding at most this many points are scanned instead of being split.
+const KNN_SCAN_SIZE: usize = 8;
+
+/// Exact k-nearest-neighbor search over points sorted by Hilbert index.
+///
+/// The points around the Hilbert index of the query give a first answer. The quadrants of the curve
+/// are then visited closest first, skipping those farther than the current k-th neighbor.
+#[derive(Clone, Debug)]
+pub struct HilbertKnn {
+    hilbert_curve: HilbertCurveAlgorithm,
+    points: Vec<(u32, CoordinateValue)>,
+}
+
+impl HilbertKnn {
+    /// Search structure over points already sorted by their Hilbert index on the `order` curve.
+    pub fn new(order: u16, points: Vec<(u32, CoordinateValue)>) -> Self {
+        if points.windows(2).any(|pair| pair[0].0 > pair[1].0) {
+            panic!("The points must be sorted by Hilbert index");
+        }
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(order),
+            points,
+        }
+    }
+
+    /// Search structure over points in any order.
+    pub fn from_points<I: IntoIterator<Item = CoordinateValue>>(order: u16, points: I) -> Self {
+        let hilbert_curve = HilbertCurveAlgorithm::new(order);
+        let mut points: Vec<(u32, CoordinateValue)> = points
+            .into_iter()
+            .map(|point| (hilbert_curve.point_to_index(point), point))
+            .collect();
+        points.sort_unstable_by_key(|(index, point)| (*index, point.x, point.y));
+        Self {
+            hilbert_curve,
+            points,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[(u32, CoordinateValue)] {
+        &self.points
+    }
+
+    /// The `k` closest points with their squared distance, closest first.
+    pub fn nearest(&self, query: CoordinateValue, k: usize) -> Vec<(CoordinateValue, u64)> {
+        self.nearest_counted(query, k).0
+    }
+
+    /// Same as `nearest`, also returning the number of points whose distance was computed.
+    pub fn nearest_counted(
+        &self,
+        query: CoordinateValue,
+        k: usize,
+    ) -> (Vec<(CoordinateValue, u64)>, usize) {
+        if k == 0 || self.points.is_empty() {
+            return (Vec::new(), 0);
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        let mut candidates = 0;
+        let mut consider = |position: usize, best: &mut BinaryHeap<(u64, usize)>| {
+            let (_, point) = self.points[position];
+            let dx = u64::from(point.x.abs_diff(query.x));
+            let dy = u64::from(point.y.abs_diff(query.y));
+            best.push((dx * dx + dy * dy, position));
+            if best.len() > k {
+                best.pop();
+            }
+            candidates += 1;
+        };
+        // Points around the position of the query along the curve
+        let key = self.hilbert_curve.point_to_index(query);
+        let middle = self.points.partition_point(|(index, _)| *index < key);
+        let window_start = middle.saturating_sub(k);
+        let window_end = (middle + k).min(self.points.len());
+        for position in window_start..window_end {
+            consider(position, &mut best);
+        }
+        let kth_distance = |best: &BinaryHeap<(u64, usize)>| {
+            if best.len() < k {
+                u64::MAX
+            } else {
+                best.peek().unwrap().0
+            }
+        };
+        let mut quadrants = BinaryHeap::new();
+        quadrants.push(Reverse((0u64, 0u32, self.hilbert_curve.order())));
+        while let Some(Reverse((distance, start, level))) = quadrants.pop() {
+            if distance > kth_distance(&best) {
+                break;
+            }
+            let end = u64::from(start) + u64::pow(4, level.into());
+            let first = self.points.partition_point(|(index, _)| *index < start);
+            let last = self
+                .points
+                .partition_point(|(index, _)| u64::from(*index) < end);
+            if first == last || (first >= window_start && last <= window_end) {
+                continue;
+            }
+            if level == 0 || last - first <= KNN_SCAN_SIZE {
+                for position in
+                    (first..last).filter(|position| !(window_start..window_end).contains(position))
+                {
+                    consider(position, &mut best);
+                }
+                continue;
+            }
+            let child_size = u32::pow(4, (level - 1).into());
+            for child in 0..4 {
+                let child_start = start + child * child_size;
+                let block = self.hilbert_curve.block_rectangle(child_start, level - 1);
+                let distance = squared_distance(&block, query);
+                if distance <= kth_distance(&best) {
+                    quadrants.push(Reverse((distance, child_start, level - 1)));
+                }
+            }
+        }
+        let neighbors = best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(distance, position)| (self.points[position].1, distance))
+            .collect();
+        (neighbors, candidates)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::collections::btree_map::{self, BTreeMap};
+
+/// Ordered map keyed by point, stored in a `BTreeMap` on the Hilbert index of the point.
+#[derive(Clone, Debug)]
+pub struct HilbertMap<V> {
+    hilbert_curve: HilbertCurveAlgorithm,
+    entries: BTreeMap<u32, V>,
+}
+
+impl<V> HilbertMap<V> {
+    pub fn new(order: u16) -> Self {
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(order),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn hilbert_curve(&self) -> &HilbertCurveAlgorithm {
+        &self.hilbert_curve
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, point: CoordinateValue, value: V) -> Option<V> {
+        let index = self.hilbert_curve.point_to_index(point);
+        self.entries.insert(index, value)
+    }
+
+    pub fn get(&self, point: CoordinateValue) -> Option<&V> {
+        self.entries.get(&self.hilbert_curve.point_to_index(point))
+    }
+
+    pub fn get_mut(&mut self, point: CoordinateValue) -> Option<&mut V> {
+        let index = self.hilbert_curve.point_to_index(point);
+        self.entries.get_mut(&index)
+    }
+
+    pub fn contains_point(&self, point: CoordinateValue) -> bool {
+        self.get(point).is_some()
+    }
+
+    pub fn remove(&mut self, point: CoordinateValue) -> Option<V> {
+        let index = self.hilbert_curve.point_to_index(point);
+        self.entries.remove(&index)
+    }
+
+    /// Entry of the point. The key of the entry is the Hilbert index of the point.
+    pub fn entry(&mut self, point: CoordinateValue) -> btree_map::Entry<'_, u32, V> {
+        let index = self.hilbert_curve.point_to_index(point);
+        self.entries.entry(index)
+    }
+
+    /// Entries in curve order.
+    pub fn iter(&self) -> impl Iterator<Item = (CoordinateValue, &V)> + '_ {
+        self.entries
+            .iter()
+            .map(|(index, value)| (self.hilbert_curve.index_to_point(*index), value))
+    }
+
+    /// Mutable entries in curve order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (CoordinateValue, &mut V)> + '_ {
+        let hilbert_curve = self.hilbert_curve;
+        self.entries
+            .iter_mut()
+            .map(move |(index, value)| (hilbert_curve.index_to_point(*index), value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.entries.values()
+    }
+
+    /// Entries inside the rectangle, in curve order, with one `BTreeMap` range scan per index range of the rectangle.
+    pub fn range_rect(
+        &self,
+        rectangle: &Rectangle,
+    ) -> impl Iterator<Item = (CoordinateValue, &V)> + '_ {
+        self.hilbert_curve
+            .ranges_for_rectangle(rectangle)
+            .into_iter()
+            .flat_map(move |range| self.entries.range(range))
+            .map(|(index, value)| (self.hilbert_curve.index_to_point(*index), value))
+    }
+}
+
+impl<V> Extend<(CoordinateValue, V)> for HilbertMap<V> {
+    fn extend<I: IntoIterator<Item = (CoordinateValue, V)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::ops::RangeInclusive;
+
+/// Set of cells of a curve stored as sorted, disjoint and non-adjacent index ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HilbertRegion {
+    hilbert_curve: HilbertCurveAlgorithm,
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl HilbertRegion {
+    /// Empty region.
+    pub fn new(order: u16) -> Self {
+        if order > 16 {
+            panic!("The order of a region must be 16 or less");
+        }
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(order),
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Region of index ranges in any order, overlapping or not.
+    pub fn from_ranges<I: IntoIterator<Item = RangeInclusive<u32>>>(order: u16, ranges: I) -> Self {
+        let mut region = Self::new(order);
+        let last_index = region.last_index();
+        let mut ranges: Vec<RangeInclusive<u32>> = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+        if ranges.iter().any(|range| *range.end() > last_index) {
+            panic!("The ranges must be inside the curve");
+        }
+        ranges.sort_unstable_by_key(|range| *range.start());
+        for range in ranges {
+            region.push_range(range);
+        }
+        region
+    }
+
+    /// Region of the cells set in a row-major mask of `width` by `height` cells.
+    pub fn from_mask(order: u16, mask: &[bool], width: u32, height: u32) -> Self {
+        let region = Self::new(order);
+        region.check_mask_size(mask.len(), width, height);
+        let mut indices: Vec<u32> = Vec::new();
+        for (position, _) in mask.iter().enumerate().filter(|(_, set)| **set) {
+            let point = CoordinateValue {
+                x: (position % width as usize) as u32,
+                y: (position / width as usize) as u32,
+            };
+            indices.push(region.hilbert_curve.point_to_index(point));
+        }
+        indices.sort_unstable();
+        region.with_sorted_indices(indices)
+    }
+
+    pub fn from_rectangle(order: u16, rectangle: &Rectangle) -> Self {
+        let mut region = Self::new(order);
+        region.ranges = region.hilbert_curve.ranges_for_rectangle(rectangle);
+        region
+    }
+
+    pub fn from_points<I: IntoIterator<Item = CoordinateValue>>(order: u16, points: I) -> Self {
+        let region = Self::new(order);
+        let mut indices: Vec<u32> = points
+            .into_iter()
+            .map(|point| region.hilbert_curve.point_to_index(point))
+            .collect();
+        indices.sort_unstable();
+        region.with_sorted_indices(indices)
+    }
+
+    fn with_sorted_indices(mut self, indices: Vec<u32>) -> Self {
+        for index in indices {
+            self.push_range(index..=index);
+        }
+        self
+    }
+
+    /// Append a range starting at or after the start of the last range, merging them when they touch.
+    fn push_range(&mut self, range: RangeInclusive<u32>) {
+        match self.ranges.last_mut() {
+            Some(last) if u64::from(*range.start()) <= u64::from(*last.end()) + 1 => {
+                if range.end() > last.end() {
+                    *last = *last.start()..=*range.end();
+                }
+            }
+            _ => self.ranges.push(range),
+        }
+    }
+
+    pub fn hilbert_curve(&self) -> &HilbertCurveAlgorithm {
+        &self.hilbert_curve
+    }
+
+    pub fn order(&self) -> u16 {
+        self.hilbert_curve.order()
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<u32>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of cells of the region.
+    pub fn area(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| u64::from(*range.end()) - u64::from(*range.start()) + 1)
+            .sum()
+    }
+
+    /// Smallest rectangle holding the region, `None` when the region is empty.
+    ///
+    /// Each range is split in aligned blocks, so the cost depends on the number of ranges and not on the area.
+    pub fn bounding_box(&self) -> Option<Rectangle> {
+        self.blocks()
+            .map(|(start, level)| self.hilbert_curve.block_rectangle(start, level))
+            .reduce(|bounds, block| bounds.union(&block))
+    }
+
+    /// Points of the region, in curve order.
+    pub fn points(&self) -> impl Iterator<Item = CoordinateValue> + '_ {
+        self.ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|index| self.hilbert_curve.index_to_point(index))
+    }
+
+    /// Row-major mask of `width` by `height` cells, the cells of the region outside of it being dropped.
+    pub fn to_mask(&self, width: u32, height: u32) -> Vec<bool> {
+        self.check_mask_size(width as usize * height as usize, width, height);
+        let mut mask = vec![false; width as usize * height as usize];
+        if mask.is_empty() {
+            return mask;
+        }
+        let area = Rectangle::new(0, 0, width - 1, height - 1);
+        for (start, level) in self.blocks() {
+            let block = self.hilbert_curve.block_rectangle(start, level);
+            if let Some(visible) = block.intersection(&area) {
+                for y in visible.y_min..=visible.y_max {
+                    let row = y as usize * width as usize;
+                    mask[row + visible.x_min as usize..=row + visible.x_max as usize].fill(true);
+                }
+            }
+        }
+        mask
+    }
+
+    pub fn contains_point(&self, point: CoordinateValue) -> bool {
+        let index = self.hilbert_curve.point_to_index(point);
+        let position = self.ranges.partition_point(|range| *range.end() < index);
+        self.ranges
+            .get(position)
+            .is_some_and(|range| range.contains(&index))
+    }
+
+    /// True when the regions share at least one cell.
+    pub fn intersects(&self, other: &HilbertRegion) -> bool {
+        self.check_same_order(other);
+        let (mut left, mut right) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            if a.end() < b.start() {
+                left.next();
+            } else if b.end() < a.start() {
+                right.next();
+            } else {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn union(&self, other: &HilbertRegion) -> HilbertRegion {
+        self.combine(other, |a, b| a || b)
+    }
+
+    pub fn intersection(&self, other: &HilbertRegion) -> HilbertRegion {
+        self.combine(other, |a, b| a && b)
+    }
+
+    /// Cells of this region that are not in `other`.
+    pub fn difference(&self, other: &HilbertRegion) -> HilbertRegion {
+        self.combine(other, |a, b| a && !b)
+    }
+
+    /// Cells in exactly one of the regions.
+    pub fn xor(&self, other: &HilbertRegion) -> HilbertRegion {
+        self.combine(other, |a, b| a != b)
+    }
+
+    /// Cells of the curve that are not in the region.
+    pub fn complement(&self) -> HilbertRegion {
+        let whole = HilbertRegion::from_ranges(self.order(), vec![0..=self.last_index()]);
+        whole.difference(self)
+    }
+
+    /// Merge the boundaries of both regions in one pass, keeping the cells where `keep` is true.
+    fn combine<F: Fn(bool, bool) -> bool>(&self, other: &HilbertRegion, keep: F) -> HilbertRegion {
+        self.check_same_order(other);
+        // Half-open boundaries: the membership flips at the start and after the end of each range
+        let boundaries = |region: &HilbertRegion| -> Vec<u64> {
+            region
+                .ranges
+                .iter()
+                .flat_map(|range| [u64::from(*range.start()), u64::from(*range.end()) + 1])
+                .collect()
+        };
+        let (left, right) = (boundaries(self), boundaries(other));
+        let (mut i, mut j) = (0, 0);
+        let (mut in_left, mut in_right, mut inside) = (false, false, false);
+        let mut start = 0;
+        let mut region = HilbertRegion::new(self.order());
+        while i < left.len() || j < right.len() {
+            let position = left
+                .get(i)
+                .copied()
+                .unwrap_or(u64::MAX)
+                .min(right.get(j).copied().unwrap_or(u64::MAX));
+            if left.get(i) == Some(&position) {
+                in_left = !in_left;
+                i += 1;
+            }
+            if right.get(j) == Some(&position) {
+                in_right = !in_right;
+                j += 1;
+            }
+            let now_inside = keep(in_left, in_right);
+            if now_inside && !inside {
+                start = position;
+            } else if !now_inside && inside {
+                region.ranges.push(start as u32..=(position - 1) as u32);
+            }
+            inside = now_inside;
+        }
+        region
+    }
+
+    fn check_same_order(&self, other: &HilbertRegion) {
+        if self.order() != other.order() {
+            panic!("The regions must have the same order");
+        }
+    }
+
+    /// Aligned blocks `(start, level)` of the ranges, in curve order.
+    pub(crate) fn blocks(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        self.ranges
+            .iter()
+            .flat_map(|range| self.hilbert_curve.range_blocks(range))
+    }
+
+    fn last_index(&self) -> u32 {
+        (u64::pow(4, self.order().into()) - 1) as u32
+    }
+
+    fn check_mask_size(&self, length: usize, width: u32, height: u32) {
+        if length != width as usize * height as usize {
+            panic!("The mask must have width times height cells");
+        }
+        let number_of_rows = self.hilbert_curve.number_of_rows();
+        if width > number_of_rows || height > number_of_rows {
+            panic!("The mask must fit in the grid of the order");
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+
+/// Consistent hashing ring over the Hilbert indices of a curve.
+///
+/// Every node places `virtual_nodes` tokens on the index space by hashing its name. A token owns the
+/// indices from the previous token, exclusive, up to itself, wrapping after the last index. Tokens
+/// landing on the same index are ordered by their full hash, so the ring only depends on its nodes.
+///
+/// Node names are hashed with FNV-1a through their `Hash` implementation, so the tokens are the same
+/// on every run. Strings and fixed-width integers hash the same on every platform, but `usize` and
+/// `isize` hash with the width of the platform: their tokens differ between 32-bit and 64-bit targets.
+///
+/// Since consecutive indices are neighboring cells, every arc is a compact area of the grid: fewer
+/// virtual nodes give more local arcs, more virtual nodes give a more even load.
+#[derive(Clone, Debug)]
+pub struct HilbertRing<N> {
+    hilbert_curve: HilbertCurveAlgorithm,
+    virtual_nodes: u32,
+    tokens: BTreeMap<(u32, u64), N>,
+    nodes: Vec<N>,
+}
+
+/// Indices whose owner changed after adding or removing a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Handoff<N> {
+    pub range: RangeInclusive<u32>,
+    pub from: N,
+    pub to: N,
+}
+
+impl<N: Clone + Eq + Hash> HilbertRing<N> {
+    pub fn new(order: u16, virtual_nodes: u32) -> Self {
+        if virtual_nodes == 0 {
+            panic!("The number of virtual nodes must be at least one");
+        }
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(order),
+            virtual_nodes,
+            tokens: BTreeMap::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn hilbert_curve(&self) -> &HilbertCurveAlgorithm {
+        &self.hilbert_curve
+    }
+
+    pub fn virtual_nodes(&self) -> u32 {
+        self.virtual_nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes in the order they were added.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Add a node and return the indices it takes over. Adding a node already in the ring moves nothing.
+    ///
+    /// Only the arcs ending at the new tokens change owner, the rest of the ring is untouched.
+    pub fn add_node(&mut self, node: N) -> Vec<Handoff<N>> {
+        if self.contains_node(&node) {
+            return Vec::new();
+        }
+        let cell_count = self.cell_count();
+        let before = self.owned_arcs();
+        for replica in 0..self.virtual_nodes {
+            self.tokens
+                .insert(token(&node, replica, cell_count), node.clone());
+        }
+        self.nodes.push(node);
+        handoffs(&before, &self.owned_arcs())
+    }
+
+    /// Remove a node and return the indices handed to the remaining nodes, or `None` when the node
+    /// is not in the ring. Removing the last node leaves the indices without owner and hands nothing off.
+    pub fn remove_node(&mut self, node: &N) -> Option<Vec<Handoff<N>>> {
+        let position = self.nodes.iter().position(|candidate| candidate == node)?;
+        let before = self.owned_arcs();
+        self.nodes.remove(position);
+        self.tokens.retain(|_, owner| owner != node);
+        Some(handoffs(&before, &self.owned_arcs()))
+    }
+
+    /// Node owning the index, `None` when the ring is empty.
+    pub fn owner(&self, index: u32) -> Option<&N> {
+        self.tokens
+            .range((index, 0)..)
+            .next()
+            .or_else(|| self.tokens.iter().next())
+            .map(|(_, node)| node)
+    }
+
+    pub fn owner_of_point(&self, point: CoordinateValue) -> Option<&N> {
+        self.owner(self.hilbert_curve.point_to_index(point))
+    }
+
+    /// Arcs of the ring in curve order, consecutive arcs of the same node merged.
+    pub fn arcs(&self) -> Vec<(RangeInclusive<u32>, &N)> {
+        let mut arcs: Vec<(RangeInclusive<u32>, &N)> = Vec::new();
+        let Some((_, first)) = self.tokens.iter().next() else {
+            return arcs;
+        };
+        let last_index = (self.cell_count() - 1) as u32;
+        let mut start = 0u64;
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|((position, _), node)| (*position, node));
+        // The indices after the last token wrap around to the first one
+        for (end, node) in tokens.chain(std::iter::once((last_index, first))) {
+            if u64::from(end) < start {
+                continue;
+            }
+            match arcs.last_mut() {
+                Some((range, owner)) if *owner == node => *range = *range.start()..=end,
+                _ => arcs.push((start as u32..=end, node)),
+            }
+            start = u64::from(end) + 1;
+        }
+        arcs
+    }
+
+    /// Nodes owning at least one cell of the rectangle, in the order the curve reaches them.
+    pub fn shards_for_rect(&self, rectangle: &Rectangle) -> Vec<&N> {
+        let mut shards: Vec<&N> = Vec::new();
+        if self.tokens.is_empty() {
+            return shards;
+        }
+        for range in self.hilbert_curve.ranges_for_rectangle(rectangle) {
+            // The indices of the range belong to the tokens inside it and to the token owning its end
+            let mut previous = None;
+            let inside = self
+                .tokens
+                .range((*range.start(), 0)..=(*range.end(), u64::MAX))
+                .filter(|((position, _), _)| previous.replace(*position) != Some(*position))
+                .map(|(_, node)| node);
+            for node in inside.chain(self.owner(*range.end())) {
+                if !shards.contains(&node) {
+                    shards.push(node);
+                }
+            }
+        }
+        shards
+    }
+
+    fn cell_count(&self) -> u64 {
+        u64::pow(4, self.hilbert_curve.order().into())
+    }
+
+    fn owned_arcs(&self) -> Vec<(RangeInclusive<u32>, N)> {
+        self.arcs()
+            .into_iter()
+            .map(|(range, node)| (range, node.clone()))
+            .collect()
+    }
+}
+
+/// Differences between two arc lists covering the whole curve, adjacent changes merged.
+fn handoffs<N: Clone + Eq>(
+    before: &[(RangeInclusive<u32>, N)],
+    after: &[(RangeInclusive<u32>, N)],
+) -> Vec<Handoff<N>> {
+    let mut moved: Vec<Handoff<N>> = Vec::new();
+    let (mut old, mut new) = (before.iter().peekable(), after.iter().peekable());
+    let mut start = 0u64;
+    while let (Some((old_range, from)), Some((new_range, to))) = (old.peek(), new.peek()) {
+        let end = (*old_range.end()).min(*new_range.end());
+        if from != to {
+            match moved.last_mut() {
+                Some(last)
+                    if last.from == *from
+                        && last.to == *to
+                        && u64::from(*last.range.end()) + 1 == start =>
+                {
+                    last.range = *last.range.start()..=end
+                }
+                _ => moved.push(Handoff {
+                    range: start as u32..=end,
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+            }
+        }
+        if *old_range.end() == end {
+            old.next();
+        }
+        if *new_range.end() == end {
+            new.next();
+        }
+        start = u64::from(end) + 1;
+    }
+    moved
+}
+
+/// Index of the token followed by its full hash.
+fn token<N: Hash>(node: &N, replica: u32, cell_count: u64) -> (u32, u64) {
+    let mut hasher = FnvHasher::default();
+    node.hash(&mut hasher);
+    replica.hash(&mut hasher);
+    let hash = mix(hasher.finish());
+    ((hash % cell_count) as u32, hash)
+}
+
+/// FNV-1a, stable across runs unlike the randomly seeded hasher of the standard library.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// Finalizer of MurmurHash3: FNV-1a barely changes the low bits when only the last byte differs,
+/// which would cluster the tokens of one node.
+fn mix(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod test_fnv_hasher {
+    use super::*;
+
+    #[test]
+    fn test_fnv_hasher() {
+        let mut hasher = FnvHasher::default();
+        assert_eq!(0xcbf29ce484222325, hasher.finish());
+        hasher.write(b"a");
+        assert_eq!(0xaf63dc4c8601ec8c, hasher.finish());
+        hasher.write(b"bc");
+        assert_eq!(0xe71fa2190541574b, hasher.finish());
+    }
+}
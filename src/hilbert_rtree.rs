@@ -0,0 +1,322 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::hilbert_sort::{apply_order, Quantizer};
+use crate::radix_sort::radix_sort_permutation;
+use crate::rectangle::Rectangle;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+const RTREE_MAGIC: &[u8; 4] = b"HRT1";
+const RTREE_KEY_ORDER: u16 = 16;
+
+/// Static R-tree packed by sorting the items on the Hilbert index of the center of their box.
+///
+/// Every level is stored in one flat vector of boxes, the items being the level 0. The node `i` of a
+/// level is the union of the boxes `i * node_size .. (i + 1) * node_size` of the level below.
+pub struct HilbertRTree<T> {
+    node_size: usize,
+    items: Vec<T>,
+    boxes: Vec<Rectangle>,
+    level_starts: Vec<usize>,
+}
+
+impl<T> HilbertRTree<T> {
+    pub fn new(items: Vec<(Rectangle, T)>, node_size: usize) -> Self {
+        if node_size < 2 {
+            panic!("The node size must be at least 2");
+        }
+        if items.len() > u32::MAX as usize {
+            panic!("The number of items must fit in u32");
+        }
+        let centers: Vec<(f64, f64)> = items
+            .iter()
+            .map(|(rectangle, _)| {
+                (
+                    (rectangle.x_min as f64 + rectangle.x_max as f64) / 2.0,
+                    (rectangle.y_min as f64 + rectangle.y_max as f64) / 2.0,
+                )
+            })
+            .collect();
+        let quantizer = Quantizer::new(&centers, RTREE_KEY_ORDER);
+        let hilbert_curve = HilbertCurveAlgorithm::new(RTREE_KEY_ORDER);
+        let keys: Vec<u32> = centers
+            .iter()
+            .map(|center| hilbert_curve.point_to_index(quantizer.quantize(*center).unwrap()))
+            .collect();
+        let positions: Vec<usize> = radix_sort_permutation(&keys)
+            .into_iter()
+            .map(|position| position as usize)
+            .collect();
+        let mut items = items;
+        apply_order(&mut items, &positions);
+        let (boxes, items): (Vec<Rectangle>, Vec<T>) = items.into_iter().unzip();
+        Self::from_sorted_parts(node_size, items, boxes)
+    }
+
+    fn from_sorted_parts(node_size: usize, items: Vec<T>, mut boxes: Vec<Rectangle>) -> Self {
+        let level_starts = Self::level_starts(node_size, items.len());
+        for level in 1..level_starts.len() - 1 {
+            let (start, end) = (level_starts[level - 1], level_starts[level]);
+            let nodes: Vec<Rectangle> = boxes[start..end]
+                .chunks(node_size)
+                .map(|children| {
+                    children[1..]
+                        .iter()
+                        .fold(children[0], |bounds, child| bounds.union(child))
+                })
+                .collect();
+            boxes.extend(nodes);
+        }
+        Self {
+            node_size,
+            items,
+            boxes,
+            level_starts,
+        }
+    }
+
+    /// Offset of each level in the flat box vector, followed by the total number of boxes.
+    fn level_starts(node_size: usize, item_count: usize) -> Vec<usize> {
+        let mut level_starts = vec![0];
+        let mut count = item_count;
+        let mut total = 0;
+        while count > 0 {
+            total += count;
+            level_starts.push(total);
+            if count == 1 {
+                break;
+            }
+            count = count.div_ceil(node_size);
+        }
+        level_starts
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn node_size(&self) -> usize {
+        self.node_size
+    }
+
+    /// Bounding box of every item, `None` when the tree is empty.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        self.boxes.last().copied()
+    }
+
+    /// Items with their box, in Hilbert order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Rectangle, &T)> {
+        self.boxes.iter().zip(self.items.iter())
+    }
+
+    /// Items whose box intersects the query, in Hilbert order.
+    pub fn search(&self, query: &Rectangle) -> Vec<&T> {
+        let mut found = Vec::new();
+        if self.is_empty() {
+            return found;
+        }
+        let root_level = self.level_starts.len() - 2;
+        let mut stack = vec![(root_level, 0)];
+        while let Some((level, position)) = stack.pop() {
+            if !self.boxes[self.level_starts[level] + position].intersects(query) {
+                continue;
+            }
+            if level == 0 {
+                found.push(&self.items[position]);
+                continue;
+            }
+            // Reversed so that the items are popped, and found, in Hilbert order
+            for child in self.children(level, position).rev() {
+                stack.push((level - 1, child));
+            }
+        }
+        found
+    }
+
+    /// The `k` items closest to the point, closest first. The distance is measured to the box of the item.
+    pub fn nearest(&self, point: CoordinateValue, k: usize) -> Vec<&T> {
+        let mut found = Vec::new();
+        if self.is_empty() || k == 0 {
+            return found;
+        }
+        let root_level = self.level_starts.len() - 2;
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((0u64, root_level, 0usize)));
+        while let Some(Reverse((_, level, position))) = queue.pop() {
+            if level == 0 {
+                found.push(&self.items[position]);
+                if found.len() == k {
+                    break;
+                }
+                continue;
+            }
+            for child in self.children(level, position) {
+                let rectangle = &self.boxes[self.level_starts[level - 1] + child];
+                queue.push(Reverse((
+                    squared_distance(rectangle, point),
+                    level - 1,
+                    child,
+                )));
+            }
+        }
+        found
+    }
+
+    fn children(&self, level: usize, position: usize) -> std::ops::Range<usize> {
+        let count = self.level_starts[level] - self.level_starts[level - 1];
+        let start = position * self.node_size;
+        start..(start + self.node_size).min(count)
+    }
+
+    /// Flat little-endian buffer: header, every box of every level, then each item as a length-prefixed payload.
+    pub fn to_bytes<F>(&self, encode_item: F) -> Vec<u8>
+    where
+        F: Fn(&T, &mut Vec<u8>),
+    {
+        let mut bytes = Vec::with_capacity(16 + self.boxes.len() * 16);
+        bytes.extend_from_slice(RTREE_MAGIC);
+        bytes.extend_from_slice(&(self.node_size as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.items.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.boxes.len() as u32).to_le_bytes());
+        for rectangle in &self.boxes {
+            for value in [
+                rectangle.x_min,
+                rectangle.y_min,
+                rectangle.x_max,
+                rectangle.y_max,
+            ] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let mut payload = Vec::new();
+        for item in &self.items {
+            payload.clear();
+            encode_item(item, &mut payload);
+            bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&payload);
+        }
+        bytes
+    }
+
+    /// Load a tree saved with `to_bytes`, without sorting or packing it again.
+    pub fn from_bytes<F>(bytes: &[u8], decode_item: F) -> Result<Self, String>
+    where
+        F: Fn(&[u8]) -> Option<T>,
+    {
+        let mut reader = ByteReader { bytes, position: 0 };
+        if reader.take(4)? != RTREE_MAGIC {
+            return Err("The buffer is not a Hilbert R-tree".to_string());
+        }
+        let node_size = reader.read_u32()? as usize;
+        let item_count = reader.read_u32()? as usize;
+        let box_count = reader.read_u32()? as usize;
+        if node_size < 2 {
+            return Err("The node size must be at least 2".to_string());
+        }
+        let level_starts = Self::level_starts(node_size, item_count);
+        if *level_starts.last().unwrap() != box_count {
+            return Err("The number of boxes does not match the number of items".to_string());
+        }
+        let mut boxes = Vec::with_capacity(box_count.min(bytes.len() / 16));
+        for _ in 0..box_count {
+            let (x_min, y_min) = (reader.read_u32()?, reader.read_u32()?);
+            let (x_max, y_max) = (reader.read_u32()?, reader.read_u32()?);
+            if x_min > x_max || y_min > y_max {
+                return Err("A box has its minimum above its maximum".to_string());
+            }
+            boxes.push(Rectangle::new(x_min, y_min, x_max, y_max));
+        }
+        let mut items = Vec::with_capacity(item_count.min(bytes.len()));
+        for _ in 0..item_count {
+            let length = reader.read_u32()? as usize;
+            let payload = reader.take(length)?;
+            items.push(decode_item(payload).ok_or("An item cannot be decoded")?);
+        }
+        if reader.position != bytes.len() {
+            return Err("There are unexpected bytes after the last item".to_string());
+        }
+        Ok(Self {
+            node_size,
+            items,
+            boxes,
+            level_starts,
+        })
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("The buffer is truncated")?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+pub(crate) fn squared_distance(rectangle: &Rectangle, point: CoordinateValue) -> u64 {
+    let axis = |value: u32, min: u32, max: u32| {
+        if value < min {
+            u64::from(min - value)
+        } else if value > max {
+            u64::from(value - max)
+        } else {
+            0
+        }
+    };
+    let dx = axis(point.x, rectangle.x_min, rectangle.x_max);
+    let dy = axis(point.y, rectangle.y_min, rectangle.y_max);
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod test_level_starts {
+    use super::*;
+
+    #[test]
+    fn test_level_starts() {
+        assert_eq!(vec![0], HilbertRTree::<()>::level_starts(4, 0));
+        assert_eq!(vec![0, 1], HilbertRTree::<()>::level_starts(4, 1));
+        assert_eq!(vec![0, 4, 5], HilbertRTree::<()>::level_starts(4, 4));
+        assert_eq!(vec![0, 10, 13, 14], HilbertRTree::<()>::level_starts(4, 10));
+    }
+}
+
+#[cfg(test)]
+mod test_squared_distance {
+    use super::*;
+
+    #[test]
+    fn test_squared_distance() {
+        let rectangle = Rectangle::new(2, 2, 4, 4);
+        assert_eq!(
+            0,
+            squared_distance(&rectangle, CoordinateValue { x: 3, y: 4 })
+        );
+        assert_eq!(
+            4,
+            squared_distance(&rectangle, CoordinateValue { x: 0, y: 3 })
+        );
+        assert_eq!(
+            2,
+            squared_distance(&rectangle, CoordinateValue { x: 5, y: 5 })
+        );
+    }
+}
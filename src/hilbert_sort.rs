@@ -0,0 +1,169 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+
+/// Sort items along the curve. The key is recomputed at each comparison, which is fine for small slices.
+pub fn hilbert_sort<T, F>(items: &mut [T], hilbert_curve: &HilbertCurveAlgorithm, key_fn: F)
+where
+    F: Fn(&T) -> CoordinateValue,
+{
+    items.sort_by_key(|item| hilbert_curve.point_to_index(key_fn(item)));
+}
+
+/// Stable sort along the curve that computes `point_to_index` once per item.
+pub fn hilbert_sort_cached<T, F>(items: &mut [T], hilbert_curve: &HilbertCurveAlgorithm, key_fn: F)
+where
+    F: Fn(&T) -> CoordinateValue,
+{
+    items.sort_by_cached_key(|item| hilbert_curve.point_to_index(key_fn(item)));
+}
+
+/// Stable sort of items with floating point coordinates.
+///
+/// The bounding box of all the points is quantized on the `order` grid before computing the
+/// Hilbert index of each item. Non-finite coordinates are sorted at the end.
+pub fn hilbert_sort_f64<T, F>(items: &mut [T], order: u16, key_fn: F)
+where
+    F: Fn(&T) -> (f64, f64),
+{
+    let points: Vec<(f64, f64)> = items.iter().map(&key_fn).collect();
+    let quantizer = Quantizer::new(&points, order);
+    let hilbert_curve = HilbertCurveAlgorithm::new(order);
+    let keys: Vec<u64> = points
+        .iter()
+        .map(|point| match quantizer.quantize(*point) {
+            Some(coordinate) => u64::from(hilbert_curve.point_to_index(coordinate)),
+            None => u64::MAX,
+        })
+        .collect();
+    let mut positions: Vec<usize> = (0..items.len()).collect();
+    positions.sort_by_key(|position| keys[*position]);
+    apply_order(items, &positions);
+}
+
+/// Map each point of the bounding box of the input onto the `2^order` grid.
+pub(crate) struct Quantizer {
+    x_min: f64,
+    y_min: f64,
+    x_scale: f64,
+    y_scale: f64,
+    maximum: u32,
+}
+
+impl Quantizer {
+    pub(crate) fn new(points: &[(f64, f64)], order: u16) -> Self {
+        if order > 16 {
+            panic!("The order must be 16 or less");
+        }
+        let finite = points
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite());
+        let (mut x_min, mut y_min) = (f64::INFINITY, f64::INFINITY);
+        let (mut x_max, mut y_max) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (x, y) in finite {
+            x_min = x_min.min(*x);
+            y_min = y_min.min(*y);
+            x_max = x_max.max(*x);
+            y_max = y_max.max(*y);
+        }
+        let maximum = u32::pow(2, order.into()) - 1;
+        let scale = |min: f64, max: f64| {
+            if max > min {
+                maximum as f64 / (max - min)
+            } else {
+                0.0
+            }
+        };
+        Self {
+            x_min,
+            y_min,
+            x_scale: scale(x_min, x_max),
+            y_scale: scale(y_min, y_max),
+            maximum,
+        }
+    }
+
+    pub(crate) fn quantize(&self, (x, y): (f64, f64)) -> Option<CoordinateValue> {
+        if !x.is_finite() || !y.is_finite() {
+            return None;
+        }
+        let cell = |value: f64, min: f64, scale: f64| {
+            (((value - min) * scale).round() as u32).min(self.maximum)
+        };
+        Some(CoordinateValue {
+            x: cell(x, self.x_min, self.x_scale),
+            y: cell(y, self.y_min, self.y_scale),
+        })
+    }
+}
+
+/// Reorder `items` so that `items[i]` becomes the item previously at `positions[i]`.
+pub(crate) fn apply_order<T>(items: &mut [T], positions: &[usize]) {
+    let mut done = vec![false; items.len()];
+    for start in 0..items.len() {
+        if done[start] {
+            continue;
+        }
+        // Follow the cycle: the slot `current` receives the item at `positions[current]`
+        let mut current = start;
+        loop {
+            done[current] = true;
+            let next = positions[current];
+            if next == start {
+                break;
+            }
+            items.swap(current, next);
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_apply_order {
+    use super::*;
+
+    #[test]
+    fn test_apply_order_cycles() {
+        let mut items = vec!['a', 'b', 'c', 'd', 'e'];
+        apply_order(&mut items, &[2, 0, 1, 4, 3]);
+        assert_eq!(vec!['c', 'a', 'b', 'e', 'd'], items);
+    }
+
+    #[test]
+    fn test_apply_order_identity() {
+        let mut items = vec![1, 2, 3];
+        apply_order(&mut items, &[0, 1, 2]);
+        assert_eq!(vec![1, 2, 3], items);
+    }
+}
+
+#[cfg(test)]
+mod test_quantizer {
+    use super::*;
+
+    #[test]
+    fn test_quantize_bounding_box_corners() {
+        let quantizer = Quantizer::new(&[(-1.0, 10.0), (1.0, 20.0)], 2);
+        assert_eq!(
+            Some(CoordinateValue { x: 0, y: 0 }),
+            quantizer.quantize((-1.0, 10.0))
+        );
+        assert_eq!(
+            Some(CoordinateValue { x: 3, y: 3 }),
+            quantizer.quantize((1.0, 20.0))
+        );
+        assert_eq!(
+            Some(CoordinateValue { x: 2, y: 2 }),
+            quantizer.quantize((0.0, 15.0))
+        );
+        assert_eq!(None, quantizer.quantize((f64::NAN, 15.0)));
+    }
+
+    #[test]
+    fn test_quantize_degenerate_box() {
+        let quantizer = Quantizer::new(&[(5.0, 5.0), (5.0, 5.0)], 4);
+        assert_eq!(
+            Some(CoordinateValue { x: 0, y: 0 }),
+            quantizer.quantize((5.0, 5.0))
+        );
+    }
+}
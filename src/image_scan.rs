@@ -0,0 +1,179 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::generalized_curve::GeneralizedHilbertCurve;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+
+/// Maximum order of the tiles walked with a precomputed table in the padded mode.
+const SCAN_TILE_ORDER: u16 = 4;
+
+/// How a curve covers an image that is not a power-of-two square.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScanMode {
+    /// Hilbert curve of the smallest square holding the image, skipping the points outside of the image.
+    Padded,
+    /// Generalized curve filling the image exactly, see `GeneralizedHilbertCurve`.
+    Generalized,
+}
+
+/// Pixels of a row-major image, in the order of the curve.
+pub fn to_hilbert_scan<P: Copy>(pixels: &[P], width: u32, height: u32, mode: ScanMode) -> Vec<P> {
+    check_length(pixels.len(), width, height);
+    let mut scan = Vec::with_capacity(pixels.len());
+    for_each_scan_position(width, height, mode, |position| scan.push(pixels[position]));
+    scan
+}
+
+/// Row-major image from pixels in the order of the curve. Inverse of `to_hilbert_scan`.
+pub fn from_hilbert_scan<P: Copy>(scan: &[P], width: u32, height: u32, mode: ScanMode) -> Vec<P> {
+    check_length(scan.len(), width, height);
+    let mut pixels = scan.to_vec();
+    let mut next = scan.iter();
+    for_each_scan_position(width, height, mode, |position| {
+        pixels[position] = *next.next().unwrap()
+    });
+    pixels
+}
+
+/// Row-major position of each pixel of the scan.
+pub fn hilbert_scan_positions(width: u32, height: u32, mode: ScanMode) -> Vec<u32> {
+    let mut positions = Vec::with_capacity(width as usize * height as usize);
+    for_each_scan_position(width, height, mode, |position| {
+        positions.push(position as u32)
+    });
+    positions
+}
+
+fn check_length(length: usize, width: u32, height: u32) {
+    if length as u64 != u64::from(width) * u64::from(height) {
+        panic!("The number of pixels must be the width times the height");
+    }
+}
+
+pub(crate) fn for_each_scan_position<F: FnMut(usize)>(
+    width: u32,
+    height: u32,
+    mode: ScanMode,
+    mut visit: F,
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let row = width as usize;
+    match mode {
+        ScanMode::Generalized => {
+            for point in GeneralizedHilbertCurve::new(width, height).iter() {
+                visit(point.y as usize * row + point.x as usize);
+            }
+        }
+        ScanMode::Padded => for_each_padded_position(width, height, visit),
+    }
+}
+
+/// Walk the tiles of `2^SCAN_TILE_ORDER` pixels along the coarse curve, and the pixels of each tile with
+/// a table. The curve inside a tile is the tile-order curve, swapped and complemented depending on the
+/// quadrants above the tile.
+fn for_each_padded_position<F: FnMut(usize)>(width: u32, height: u32, mut visit: F) {
+    let order = order_for_size(width.max(height));
+    let tile_order = order.min(SCAN_TILE_ORDER);
+    let tile_curve = HilbertCurveAlgorithm::new(tile_order);
+    let tile_side = tile_curve.number_of_rows();
+    let tables = tile_tables(&tile_curve);
+    let coarse_curve = HilbertCurveAlgorithm::new(order - tile_order);
+    let row = width as usize;
+    for (tile_index, tile) in coarse_curve.iter().enumerate() {
+        let x_min = tile.x * tile_side;
+        let y_min = tile.y * tile_side;
+        if x_min >= width || y_min >= height {
+            continue;
+        }
+        let table = &tables[tile_state(tile_index as u32, coarse_curve.order())];
+        let inside = x_min + tile_side <= width && y_min + tile_side <= height;
+        for offset in table {
+            let (x, y) = (x_min + offset.x, y_min + offset.y);
+            if inside || (x < width && y < height) {
+                visit(y as usize * row + x as usize);
+            }
+        }
+    }
+}
+
+/// Points of the tile curve for the four states: plain, swapped, complemented, swapped and complemented.
+fn tile_tables(tile_curve: &HilbertCurveAlgorithm) -> [Vec<CoordinateValue>; 4] {
+    let mask = tile_curve.number_of_rows() - 1;
+    [(false, false), (true, false), (false, true), (true, true)].map(|(swap, complement)| {
+        tile_curve
+            .iter()
+            .map(|point| {
+                let point = if complement {
+                    CoordinateValue {
+                        x: point.x ^ mask,
+                        y: point.y ^ mask,
+                    }
+                } else {
+                    point
+                };
+                if swap {
+                    CoordinateValue {
+                        x: point.y,
+                        y: point.x,
+                    }
+                } else {
+                    point
+                }
+            })
+            .collect()
+    })
+}
+
+/// Table of a tile from the digits of its coarse index: `0` and `3` swap, `3` also complements.
+fn tile_state(coarse_index: u32, coarse_order: u16) -> usize {
+    let mut state = 0;
+    for level in 0..u32::from(coarse_order) {
+        match (coarse_index >> (2 * level)) & 3 {
+            0 => state ^= 1,
+            3 => state ^= 3,
+            _ => {}
+        }
+    }
+    state
+}
+
+/// Smallest order whose grid holds `size` rows.
+pub(crate) fn order_for_size(size: u32) -> u16 {
+    (u32::BITS - size.saturating_sub(1).leading_zeros()) as u16
+}
+
+#[cfg(test)]
+mod test_for_each_padded_position {
+    use super::*;
+
+    fn unblocked_positions(width: u32, height: u32) -> Vec<usize> {
+        HilbertCurveAlgorithm::new(order_for_size(width.max(height)))
+            .iter()
+            .filter(|point| point.x < width && point.y < height)
+            .map(|point| point.y as usize * width as usize + point.x as usize)
+            .collect()
+    }
+
+    #[test]
+    fn test_same_as_unblocked_curve() {
+        for (width, height) in [(1, 1), (2, 3), (16, 16), (37, 21), (300, 17), (64, 128)] {
+            let mut positions = Vec::new();
+            for_each_padded_position(width, height, |position| positions.push(position));
+            assert_eq!(unblocked_positions(width, height), positions);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_order_for_size {
+    use super::*;
+
+    #[test]
+    fn test_order_for_size() {
+        assert_eq!(0, order_for_size(1));
+        assert_eq!(1, order_for_size(2));
+        assert_eq!(2, order_for_size(3));
+        assert_eq!(2, order_for_size(4));
+        assert_eq!(13, order_for_size(7680));
+    }
+}
@@ -0,0 +1,122 @@
+//! Map of the IPv4 address space on an order 16 curve, one cell per address.
+//!
+//! Following the Hilbert curve keeps every CIDR block contiguous: a block with an even number of
+//! host bits is a square and a block with an odd number of host bits is a 2:1 rectangle.
+use crate::color::{Colormap, Rgb};
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::raster::RasterImage;
+use crate::rectangle::Rectangle;
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+
+pub const IPV4_ORDER: u16 = 16;
+
+pub fn ip_to_point(ip: Ipv4Addr) -> CoordinateValue {
+    HilbertCurveAlgorithm::new(IPV4_ORDER).index_to_point(u32::from(ip))
+}
+
+pub fn point_to_ip(point: CoordinateValue) -> Ipv4Addr {
+    Ipv4Addr::from(HilbertCurveAlgorithm::new(IPV4_ORDER).point_to_index(point))
+}
+
+/// Rectangle covered by a CIDR block. The host bits of `network` are ignored.
+pub fn cidr_to_rectangle(network: Ipv4Addr, prefix: u8) -> Rectangle {
+    if prefix > 32 {
+        panic!("The prefix must be between 0 and 32");
+    }
+    let host_bits = 32 - u32::from(prefix);
+    let first = block_start(u32::from(network), host_bits);
+    let hilbert_curve = HilbertCurveAlgorithm::new(IPV4_ORDER);
+    let square = hilbert_curve.block_rectangle(first, (host_bits / 2) as u16);
+    if host_bits.is_multiple_of(2) {
+        square
+    } else {
+        // Two consecutive squares of the same parent quadrant always share a side
+        let second_start = first + (1 << (host_bits - 1));
+        square.union(&hilbert_curve.block_rectangle(second_start, (host_bits / 2) as u16))
+    }
+}
+
+/// CIDR block of the given prefix that contains the address at the point.
+pub fn point_to_cidr(point: CoordinateValue, prefix: u8) -> (Ipv4Addr, u8) {
+    if prefix > 32 {
+        panic!("The prefix must be between 0 and 32");
+    }
+    let ip = u32::from(point_to_ip(point));
+    (
+        Ipv4Addr::from(block_start(ip, 32 - u32::from(prefix))),
+        prefix,
+    )
+}
+
+/// Heat-map of address counts aggregated per block of `block_prefix` (for example 24 for one pixel per /24).
+///
+/// The image is `2^(block_prefix / 2)` pixels wide: 4096 for /24, 256 for /16. Blocks without any
+/// count keep the `background` color.
+pub fn render_heatmap<I>(
+    counts: I,
+    block_prefix: u8,
+    colormap: Colormap,
+    log_scale: bool,
+    background: Rgb,
+) -> RasterImage
+where
+    I: IntoIterator<Item = (Ipv4Addr, u64)>,
+{
+    if !block_prefix.is_multiple_of(2) || block_prefix > 24 {
+        panic!("The block prefix must be even and at most 24");
+    }
+    let host_bits = 32 - u32::from(block_prefix);
+    let mut blocks: HashMap<u32, u64> = HashMap::new();
+    for (ip, count) in counts {
+        let block = (u64::from(u32::from(ip)) >> host_bits) as u32;
+        *blocks.entry(block).or_insert(0) += count;
+    }
+
+    let hilbert_curve = HilbertCurveAlgorithm::new(u16::from(block_prefix / 2));
+    let width = hilbert_curve.number_of_rows();
+    let mut image = RasterImage::new(width, width, background);
+    let scale = |count: u64| {
+        if log_scale {
+            (count as f64).ln_1p()
+        } else {
+            count as f64
+        }
+    };
+    let maximum = blocks.values().copied().map(scale).fold(0.0, f64::max);
+    for (block, count) in blocks {
+        if count == 0 {
+            continue;
+        }
+        let t = if maximum > 0.0 {
+            scale(count) / maximum
+        } else {
+            1.0
+        };
+        let point = hilbert_curve.index_to_point(block);
+        image.set_pixel(point.x, point.y, colormap.color(t));
+    }
+    image
+}
+
+fn block_start(address: u32, host_bits: u32) -> u32 {
+    if host_bits == 32 {
+        0
+    } else {
+        address & !((1u32 << host_bits) - 1)
+    }
+}
+
+#[cfg(test)]
+mod test_block_start {
+    use super::*;
+
+    #[test]
+    fn test_block_start() {
+        assert_eq!(0x0A000000, block_start(0x0A0B0C0D, 24));
+        assert_eq!(0x0A0B0C00, block_start(0x0A0B0C0D, 8));
+        assert_eq!(0x0A0B0C0D, block_start(0x0A0B0C0D, 0));
+        assert_eq!(0, block_start(0x0A0B0C0D, 32));
+    }
+}
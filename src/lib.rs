@@ -1,5 +1,61 @@
 mod hilbert_curve_algorithm;
 mod coordinate_value;
+mod color;
+mod svg;
+pub mod raster;
+mod ascii;
+mod rectangle;
+pub mod ipv4;
+mod sequence_layout;
+mod hilbert_sort;
+mod radix_sort;
+mod parallel;
+mod permutation;
+mod hilbert_rtree;
+mod hilbert_map;
+mod hilbert_cell;
+mod linear_quadtree;
+mod direction;
+mod hilbert_grid;
+mod generalized_curve;
+mod image_scan;
+pub mod texture;
+pub mod codec;
+pub mod dither;
+mod hilbert_region;
+mod polygon;
+mod region_coverer;
+mod circle;
+pub mod geometry;
+mod hilbert_knn;
+mod spatial_join;
+mod partition;
+mod hilbert_ring;
 
-pub use self::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+pub use self::hilbert_curve_algorithm::{HilbertCurveAlgorithm, HilbertCurveIterator};
 pub use self::coordinate_value::CoordinateValue;
+pub use self::color::{Colormap, Rgb};
+pub use self::svg::{render_svg, SvgOptions};
+pub use self::ascii::{render_ascii, render_ascii_indices};
+pub use self::rectangle::Rectangle;
+pub use self::sequence_layout::{layout_sequence, Aggregation, HilbertLayout};
+pub use self::hilbert_sort::{hilbert_sort, hilbert_sort_cached, hilbert_sort_f64};
+pub use self::radix_sort::{radix_sort_permutation, RadixKey};
+pub use self::parallel::{par_hilbert_sort, par_indices_to_points, par_points_to_indices};
+pub use self::permutation::{apply_permutation, hilbert_permutation, invert_permutation};
+pub use self::hilbert_rtree::HilbertRTree;
+pub use self::hilbert_map::HilbertMap;
+pub use self::hilbert_cell::HilbertCell;
+pub use self::linear_quadtree::LinearQuadtree;
+pub use self::direction::Direction;
+pub use self::hilbert_grid::HilbertGrid;
+pub use self::generalized_curve::{GeneralizedHilbertCurve, GeneralizedHilbertIterator};
+pub use self::image_scan::{from_hilbert_scan, hilbert_scan_positions, to_hilbert_scan, ScanMode};
+pub use self::hilbert_region::HilbertRegion;
+pub use self::polygon::Polygon;
+pub use self::region_coverer::{BlockRelation, CoverableRegion, RegionCoverer};
+pub use self::circle::Circle;
+pub use self::hilbert_knn::HilbertKnn;
+pub use self::spatial_join::{join_intersecting, join_within_distance};
+pub use self::partition::{partition, partition_points, rebalance, Partition, Rebalance};
+pub use self::hilbert_ring::{Handoff, HilbertRing};
@@ -0,0 +1,258 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_cell::HilbertCell;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Quadtree storing only its leaves, sorted along the curve.
+///
+/// The leaves partition the grid of the `max_order` curve: the leaf holding a point is the last leaf
+/// starting at or before the Hilbert index of the point.
+#[derive(Clone, Debug)]
+pub struct LinearQuadtree {
+    hilbert_curve: HilbertCurveAlgorithm,
+    capacity: usize,
+    leaves: BTreeMap<u32, Leaf>,
+    point_count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Leaf {
+    cell: HilbertCell,
+    points: Vec<CoordinateValue>,
+}
+
+impl LinearQuadtree {
+    /// Empty quadtree over the grid of the `max_order` curve. A leaf splits when it holds more than `capacity` points.
+    pub fn new(max_order: u16, capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("The capacity must be at least one point");
+        }
+        let mut leaves = BTreeMap::new();
+        leaves.insert(
+            0,
+            Leaf {
+                cell: HilbertCell::root(),
+                points: Vec::new(),
+            },
+        );
+        Self {
+            hilbert_curve: HilbertCurveAlgorithm::new(max_order),
+            capacity,
+            leaves,
+            point_count: 0,
+        }
+    }
+
+    pub fn max_order(&self) -> u16 {
+        self.hilbert_curve.order()
+    }
+
+    pub fn len(&self) -> usize {
+        self.point_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.point_count == 0
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Leaves in curve order with their points.
+    pub fn leaves(&self) -> impl Iterator<Item = (HilbertCell, &[CoordinateValue])> + '_ {
+        self.leaves
+            .values()
+            .map(|leaf| (leaf.cell, leaf.points.as_slice()))
+    }
+
+    /// Leaf containing the point.
+    pub fn locate(&self, point: CoordinateValue) -> HilbertCell {
+        let index = self.hilbert_curve.point_to_index(point);
+        self.leaf_key(index).1.cell
+    }
+
+    pub fn insert(&mut self, point: CoordinateValue) {
+        let index = self.hilbert_curve.point_to_index(point);
+        let key = self.leaf_key(index).0;
+        let leaf = self.leaves.get_mut(&key).unwrap();
+        leaf.points.push(point);
+        self.point_count += 1;
+        if leaf.points.len() > self.capacity {
+            self.split_over_capacity(key);
+        }
+    }
+
+    /// Remove one occurrence of the point, merging the siblings that end up under the capacity.
+    pub fn remove(&mut self, point: CoordinateValue) -> bool {
+        let index = self.hilbert_curve.point_to_index(point);
+        let key = self.leaf_key(index).0;
+        let leaf = self.leaves.get_mut(&key).unwrap();
+        match leaf.points.iter().position(|candidate| *candidate == point) {
+            Some(position) => {
+                leaf.points.swap_remove(position);
+                self.point_count -= 1;
+                let cell = leaf.cell;
+                self.merge_underfull(cell);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Points inside the rectangle, in leaf order.
+    pub fn query_rect(&self, rectangle: &Rectangle) -> Vec<CoordinateValue> {
+        let mut keys = BTreeSet::new();
+        for range in self.hilbert_curve.ranges_for_rectangle(rectangle) {
+            keys.insert(self.leaf_key(*range.start()).0);
+            keys.extend(self.leaves.range(range).map(|(key, _)| *key));
+        }
+        let mut found = Vec::new();
+        for key in keys {
+            let leaf = &self.leaves[&key];
+            if rectangle.contains_rectangle(&leaf.cell.bounds(self.max_order())) {
+                found.extend_from_slice(&leaf.points);
+            } else {
+                found.extend(
+                    leaf.points
+                        .iter()
+                        .filter(|point| rectangle.contains(**point)),
+                );
+            }
+        }
+        found
+    }
+
+    /// Split leaves until two leaves sharing an edge differ by at most one order. Returns the number of splits.
+    pub fn balance(&mut self) -> usize {
+        let max_order = self.max_order();
+        let number_of_rows = self.hilbert_curve.number_of_rows();
+        let mut splits = 0;
+        let mut pending: Vec<HilbertCell> = self.leaves.values().map(|leaf| leaf.cell).collect();
+        while let Some(cell) = pending.pop() {
+            if cell.order() < 2 || !self.is_leaf(&cell) {
+                continue;
+            }
+            let bounds = cell.bounds(max_order);
+            let mut neighbors = Vec::with_capacity(4);
+            if bounds.x_min > 0 {
+                neighbors.push(CoordinateValue {
+                    x: bounds.x_min - 1,
+                    y: bounds.y_min,
+                });
+            }
+            if bounds.y_min > 0 {
+                neighbors.push(CoordinateValue {
+                    x: bounds.x_min,
+                    y: bounds.y_min - 1,
+                });
+            }
+            if bounds.x_max + 1 < number_of_rows {
+                neighbors.push(CoordinateValue {
+                    x: bounds.x_max + 1,
+                    y: bounds.y_min,
+                });
+            }
+            if bounds.y_max + 1 < number_of_rows {
+                neighbors.push(CoordinateValue {
+                    x: bounds.x_min,
+                    y: bounds.y_max + 1,
+                });
+            }
+            for neighbor in neighbors {
+                let (key, leaf) = self.leaf_key(self.hilbert_curve.point_to_index(neighbor));
+                if leaf.cell.order() + 1 < cell.order() {
+                    let children = self.split(key);
+                    splits += 1;
+                    pending.extend(children);
+                    // The neighbor may still be too large after one split
+                    pending.push(cell);
+                }
+            }
+        }
+        splits
+    }
+
+    fn is_leaf(&self, cell: &HilbertCell) -> bool {
+        let key = *cell.index_range(self.max_order()).start();
+        self.leaves.get(&key).is_some_and(|leaf| leaf.cell == *cell)
+    }
+
+    fn leaf_key(&self, index: u32) -> (u32, &Leaf) {
+        let (key, leaf) = self.leaves.range(..=index).next_back().unwrap();
+        (*key, leaf)
+    }
+
+    fn split(&mut self, key: u32) -> [HilbertCell; 4] {
+        let leaf = self.leaves.remove(&key).unwrap();
+        let children = leaf.cell.children();
+        let max_order = self.max_order();
+        for child in children {
+            self.leaves.insert(
+                *child.index_range(max_order).start(),
+                Leaf {
+                    cell: child,
+                    points: Vec::new(),
+                },
+            );
+        }
+        for point in leaf.points {
+            let index = self.hilbert_curve.point_to_index(point);
+            let child_key = self.leaf_key(index).0;
+            self.leaves.get_mut(&child_key).unwrap().points.push(point);
+        }
+        children
+    }
+
+    fn split_over_capacity(&mut self, key: u32) {
+        let mut pending = vec![key];
+        while let Some(key) = pending.pop() {
+            let leaf = &self.leaves[&key];
+            if leaf.points.len() <= self.capacity || leaf.cell.order() == self.max_order() {
+                continue;
+            }
+            for child in self.split(key) {
+                pending.push(*child.index_range(self.max_order()).start());
+            }
+        }
+    }
+
+    fn merge_underfull(&mut self, cell: HilbertCell) {
+        let max_order = self.max_order();
+        let mut current = cell;
+        while let Some(parent) = current.parent() {
+            let siblings = parent.children();
+            if !siblings.iter().all(|sibling| self.is_leaf(sibling)) {
+                return;
+            }
+            let total: usize = siblings
+                .iter()
+                .map(|sibling| {
+                    self.leaves[sibling.index_range(max_order).start()]
+                        .points
+                        .len()
+                })
+                .sum();
+            if total > self.capacity {
+                return;
+            }
+            let mut points = Vec::with_capacity(total);
+            for sibling in siblings {
+                let leaf = self
+                    .leaves
+                    .remove(sibling.index_range(max_order).start())
+                    .unwrap();
+                points.extend(leaf.points);
+            }
+            self.leaves.insert(
+                *parent.index_range(max_order).start(),
+                Leaf {
+                    cell: parent,
+                    points,
+                },
+            );
+            current = parent;
+        }
+    }
+}
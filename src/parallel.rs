@@ -0,0 +1,87 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::hilbert_sort::apply_order;
+use crate::radix_sort::par_radix_sort_pairs;
+use std::thread;
+
+/// `point_to_index` of every point, computed by `threads` scoped threads on contiguous chunks.
+pub fn par_points_to_indices(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    points: &[CoordinateValue],
+    threads: usize,
+) -> Vec<u32> {
+    par_keys(points, threads, |point| {
+        hilbert_curve.point_to_index(*point)
+    })
+}
+
+/// `index_to_point` of every index, computed by `threads` scoped threads on contiguous chunks.
+pub fn par_indices_to_points(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    indices: &[u32],
+    threads: usize,
+) -> Vec<CoordinateValue> {
+    par_keys(indices, threads, |index| {
+        hilbert_curve.index_to_point(*index)
+    })
+}
+
+/// Parallel stable sort along the curve, with the same result as `hilbert_sort_cached`.
+///
+/// The keys are computed in parallel, sorted with a parallel radix sort, and the items are moved
+/// once in their final position.
+pub fn par_hilbert_sort<T, F>(
+    items: &mut [T],
+    hilbert_curve: &HilbertCurveAlgorithm,
+    key_fn: F,
+    threads: usize,
+) where
+    T: Sync,
+    F: Fn(&T) -> CoordinateValue + Sync,
+{
+    if items.len() > u32::MAX as usize {
+        panic!("The number of items must fit in u32");
+    }
+    let keys = par_keys(items, threads, |item| {
+        hilbert_curve.point_to_index(key_fn(item))
+    });
+    let pairs: Vec<(u32, u32)> = keys
+        .into_iter()
+        .enumerate()
+        .map(|(position, key)| (key, position as u32))
+        .collect();
+    let positions: Vec<usize> = par_radix_sort_pairs(pairs, threads)
+        .into_iter()
+        .map(|(_, position)| position as usize)
+        .collect();
+    apply_order(items, &positions);
+}
+
+fn par_keys<T, K, F>(items: &[T], threads: usize, key_fn: F) -> Vec<K>
+where
+    T: Sync,
+    K: Send,
+    F: Fn(&T) -> K + Sync,
+{
+    if threads == 0 {
+        panic!("The number of threads must be at least one");
+    }
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    let chunks: Vec<Vec<K>> = thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let key_fn = &key_fn;
+                scope.spawn(move || chunk.iter().map(key_fn).collect::<Vec<K>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    chunks.into_iter().flatten().collect()
+}
@@ -0,0 +1,238 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::rectangle::Rectangle;
+use std::ops::RangeInclusive;
+
+/// Contiguous piece of the curve with the weight of its items and the rectangles it covers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    range: RangeInclusive<u32>,
+    weight: u64,
+    rectangles: Vec<Rectangle>,
+}
+
+impl Partition {
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    /// Rectangles covering exactly the cells of the range, see `range_to_rectangles`.
+    pub fn rectangles(&self) -> &[Rectangle] {
+        &self.rectangles
+    }
+
+    pub fn bounding_box(&self) -> Rectangle {
+        self.rectangles[1..]
+            .iter()
+            .fold(self.rectangles[0], |bounds, rectangle| {
+                bounds.union(rectangle)
+            })
+    }
+}
+
+/// Result of `rebalance`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rebalance {
+    pub partitions: Vec<Partition>,
+    /// Number of boundaries that moved.
+    pub moved_boundaries: usize,
+    /// Weight of the items now owned by another piece.
+    pub moved_weight: u64,
+}
+
+/// Split the whole curve in `pieces` contiguous ranges of about the same weight.
+///
+/// Items are `(Hilbert index, weight)` pairs in any order. Items sharing an index are never split.
+/// Each piece is a `Partition` holding an inclusive range rather than a half-open `Range<u32>`, since
+/// the last index of an order 16 curve is `u32::MAX`, along with its weight and rectangles.
+pub fn partition(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    items: &[(u32, u64)],
+    pieces: usize,
+) -> Vec<Partition> {
+    let weights = KeyWeights::new(items);
+    let starts: Vec<u64> = (1..pieces)
+        .map(|piece| weights.ideal_start(piece, pieces))
+        .collect();
+    build(hilbert_curve, &weights, starts, pieces)
+}
+
+/// Same as `partition`, every point weighing 1.
+pub fn partition_points(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    points: &[CoordinateValue],
+    pieces: usize,
+) -> Vec<Partition> {
+    let items: Vec<(u32, u64)> = points
+        .iter()
+        .map(|point| (hilbert_curve.point_to_index(*point), 1))
+        .collect();
+    partition(hilbert_curve, &items, pieces)
+}
+
+/// Adjust the ranges of a previous partition to new items, moving as little weight as possible.
+///
+/// A boundary stays in place while the weight before it is within `tolerance` times the ideal piece
+/// weight of its ideal value. Otherwise it moves only until it reaches that margin.
+pub fn rebalance(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    current: &[RangeInclusive<u32>],
+    items: &[(u32, u64)],
+    tolerance: f64,
+) -> Rebalance {
+    if current.is_empty() {
+        panic!("The current partition must have at least one range");
+    }
+    if tolerance.is_nan() || tolerance < 0.0 {
+        panic!("The tolerance must be positive");
+    }
+    let pieces = current.len();
+    let weights = KeyWeights::new(items);
+    let margin = tolerance * weights.total as f64 / pieces as f64;
+    let mut moved_boundaries = 0;
+    let mut moved_weight = 0;
+    let mut starts = Vec::with_capacity(pieces - 1);
+    for (piece, range) in current.iter().enumerate().skip(1) {
+        let start = u64::from(*range.start());
+        let ideal = weights.total as f64 * piece as f64 / pieces as f64;
+        let before = weights.weight_before(start);
+        let target = if (before as f64) < ideal - margin {
+            Some(ideal - margin)
+        } else if before as f64 > ideal + margin {
+            Some(ideal + margin)
+        } else {
+            None
+        };
+        let new_start = match target {
+            None => start,
+            Some(target) => weights.closest_start(start, target),
+        };
+        if new_start != start {
+            moved_boundaries += 1;
+            moved_weight += before.abs_diff(weights.weight_before(new_start));
+        }
+        starts.push(new_start);
+    }
+    Rebalance {
+        partitions: build(hilbert_curve, &weights, starts, pieces),
+        moved_boundaries,
+        moved_weight,
+    }
+}
+
+/// Partitions from the starts of every piece but the first, made strictly increasing inside the curve.
+fn build(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    weights: &KeyWeights,
+    mut starts: Vec<u64>,
+    pieces: usize,
+) -> Vec<Partition> {
+    let cell_count = u64::from(hilbert_curve.number_of_rows()).pow(2);
+    if pieces == 0 || pieces as u64 > cell_count {
+        panic!("The number of pieces must be between 1 and the number of cells");
+    }
+    starts.insert(0, 0);
+    for piece in 1..pieces {
+        starts[piece] = starts[piece].max(starts[piece - 1] + 1);
+    }
+    for piece in (1..pieces).rev() {
+        starts[piece] = starts[piece].min(cell_count - (pieces - piece) as u64);
+    }
+    starts.push(cell_count);
+    starts
+        .windows(2)
+        .map(|pair| {
+            let range = pair[0] as u32..=(pair[1] - 1) as u32;
+            Partition {
+                weight: weights.weight_before(pair[1]) - weights.weight_before(pair[0]),
+                rectangles: hilbert_curve.range_to_rectangles(&range),
+                range,
+            }
+        })
+        .collect()
+}
+
+/// Distinct sorted keys with the total weight before each of them.
+struct KeyWeights {
+    keys: Vec<u64>,
+    /// `prefix[k]` is the weight of the keys before `keys[k]`, with one more entry for the total.
+    prefix: Vec<u64>,
+    total: u64,
+}
+
+impl KeyWeights {
+    fn new(items: &[(u32, u64)]) -> Self {
+        let mut items = items.to_vec();
+        items.sort_unstable_by_key(|(key, _)| *key);
+        let mut keys: Vec<u64> = Vec::new();
+        let mut prefix = vec![0];
+        for (key, weight) in items {
+            if keys.last() != Some(&u64::from(key)) {
+                keys.push(u64::from(key));
+                prefix.push(*prefix.last().unwrap());
+            }
+            *prefix.last_mut().unwrap() += weight;
+        }
+        let total = *prefix.last().unwrap();
+        Self {
+            keys,
+            prefix,
+            total,
+        }
+    }
+
+    fn weight_before(&self, start: u64) -> u64 {
+        self.prefix[self.keys.partition_point(|key| *key < start)]
+    }
+
+    /// Start of the piece `piece` out of `pieces` for the most even weights.
+    fn ideal_start(&self, piece: usize, pieces: usize) -> u64 {
+        let target = u128::from(self.total) * piece as u128;
+        let scaled = |k: usize| u128::from(self.prefix[k]) * pieces as u128;
+        let mut k = self
+            .prefix
+            .partition_point(|weight| u128::from(*weight) * (pieces as u128) < target);
+        k = k.min(self.keys.len());
+        if k > 0 && target - scaled(k - 1).min(target) <= scaled(k).saturating_sub(target) {
+            k -= 1;
+        }
+        self.start_of(k)
+    }
+
+    /// Start with the weight before it closest to `target`, the closest to `current` among equal choices.
+    fn closest_start(&self, current: u64, target: f64) -> u64 {
+        let above = self
+            .prefix
+            .partition_point(|weight| (*weight as f64) < target)
+            .min(self.keys.len());
+        let starts = |k: usize| {
+            // Every start after the previous key and up to this key leaves the same weight before it
+            let lowest = if k == 0 { 0 } else { self.keys[k - 1] + 1 };
+            current.clamp(lowest, self.start_of(k))
+        };
+        if above == 0 {
+            return starts(0);
+        }
+        let below_distance = target - self.prefix[above - 1] as f64;
+        let above_distance = self.prefix[above] as f64 - target;
+        if below_distance < above_distance
+            || (below_distance == above_distance
+                && starts(above - 1).abs_diff(current) <= starts(above).abs_diff(current))
+        {
+            starts(above - 1)
+        } else {
+            starts(above)
+        }
+    }
+
+    fn start_of(&self, k: usize) -> u64 {
+        match self.keys.get(k) {
+            Some(key) => *key,
+            None => self.keys.last().map_or(0, |key| key + 1),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::radix_sort::radix_sort_permutation;
+
+/// Positions of the points in Hilbert order: `points[result[0]]` is the first point along the curve.
+///
+/// Points with the same index keep their original order.
+pub fn hilbert_permutation(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    points: &[CoordinateValue],
+) -> Vec<u32> {
+    let keys: Vec<u32> = points
+        .iter()
+        .map(|point| hilbert_curve.point_to_index(*point))
+        .collect();
+    radix_sort_permutation(&keys)
+}
+
+/// New vector where the item `i` is `items[permutation[i]]`. Use it on each column to reorder them consistently.
+pub fn apply_permutation<T: Clone>(items: &[T], permutation: &[u32]) -> Vec<T> {
+    if items.len() != permutation.len() {
+        panic!("The permutation must have the same length as the items");
+    }
+    permutation
+        .iter()
+        .map(|position| items[*position as usize].clone())
+        .collect()
+}
+
+/// Permutation that restores the original order: `apply_permutation(sorted, &invert_permutation(p))`.
+pub fn invert_permutation(permutation: &[u32]) -> Vec<u32> {
+    let mut inverse = vec![u32::MAX; permutation.len()];
+    for (position, target) in permutation.iter().enumerate() {
+        let slot = inverse
+            .get_mut(*target as usize)
+            .filter(|slot| **slot == u32::MAX);
+        match slot {
+            Some(slot) => *slot = position as u32,
+            None => panic!("The permutation must contain every position exactly once"),
+        }
+    }
+    inverse
+}
@@ -0,0 +1,130 @@
+use crate::rectangle::Rectangle;
+
+/// Polygon in grid units: the cell `(x, y)` is the unit square from `(x, y)` to `(x + 1, y + 1)`.
+///
+/// The inside is defined with the even-odd rule over every ring, so holes and rings touching
+/// themselves need no particular orientation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+    exterior: Vec<(f64, f64)>,
+    holes: Vec<Vec<(f64, f64)>>,
+}
+
+impl Polygon {
+    /// Polygon from its exterior ring. The ring closes itself, repeating the first vertex is optional.
+    pub fn new(exterior: Vec<(f64, f64)>) -> Self {
+        Self::with_holes(exterior, Vec::new())
+    }
+
+    pub fn with_holes(exterior: Vec<(f64, f64)>, holes: Vec<Vec<(f64, f64)>>) -> Self {
+        if exterior.len() < 3 || holes.iter().any(|hole| hole.len() < 3) {
+            panic!("A ring must have at least 3 vertices");
+        }
+        let all_finite = exterior
+            .iter()
+            .chain(holes.iter().flatten())
+            .all(|(x, y)| x.is_finite() && y.is_finite());
+        if !all_finite {
+            panic!("The vertices must be finite numbers");
+        }
+        Self { exterior, holes }
+    }
+
+    pub fn exterior(&self) -> &[(f64, f64)] {
+        &self.exterior
+    }
+
+    pub fn holes(&self) -> &[Vec<(f64, f64)>] {
+        &self.holes
+    }
+
+    /// Smallest box holding the exterior ring, as `(x_min, y_min, x_max, y_max)`.
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        self.exterior.iter().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(x_min, y_min, x_max, y_max), (x, y)| {
+                (x_min.min(*x), y_min.min(*y), x_max.max(*x), y_max.max(*y))
+            },
+        )
+    }
+
+    /// True when the position is inside, with the even-odd rule.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let mut inside = false;
+        for ((x1, y1), (x2, y2)) in self.edges() {
+            if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// True when an edge goes through the interior of the cells of the rectangle.
+    pub(crate) fn crosses(&self, rectangle: &Rectangle) -> bool {
+        let (x_min, y_min) = (f64::from(rectangle.x_min), f64::from(rectangle.y_min));
+        let (x_max, y_max) = (
+            f64::from(rectangle.x_max) + 1.0,
+            f64::from(rectangle.y_max) + 1.0,
+        );
+        self.edges().any(|((x1, y1), (x2, y2))| {
+            // Liang-Barsky clipping of the edge to the closed box
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let (mut t0, mut t1) = (0.0f64, 1.0f64);
+            for (p, q) in [
+                (-dx, x1 - x_min),
+                (dx, x_max - x1),
+                (-dy, y1 - y_min),
+                (dy, y_max - y1),
+            ] {
+                if p == 0.0 {
+                    if q < 0.0 {
+                        return false;
+                    }
+                } else if p < 0.0 {
+                    t0 = t0.max(q / p);
+                } else {
+                    t1 = t1.min(q / p);
+                }
+            }
+            if t0 >= t1 {
+                return false;
+            }
+            // A clipped chord is inside the open box unless it lies on a side, and then its middle does too
+            let (x, y) = (x1 + dx * (t0 + t1) / 2.0, y1 + dy * (t0 + t1) / 2.0);
+            x > x_min && x < x_max && y > y_min && y < y_max
+        })
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        std::iter::once(&self.exterior)
+            .chain(self.holes.iter())
+            .flat_map(|ring| {
+                ring.iter()
+                    .zip(ring.iter().cycle().skip(1))
+                    .map(|(from, to)| (*from, *to))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test_crosses {
+    use super::*;
+
+    #[test]
+    fn test_crosses() {
+        let triangle = Polygon::new(vec![(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        assert!(triangle.crosses(&Rectangle::new(3, 3, 4, 4)));
+        assert!(!triangle.crosses(&Rectangle::new(0, 0, 1, 1)));
+        assert!(!triangle.crosses(&Rectangle::new(6, 6, 7, 7)));
+        // An edge along the side of the cells does not go through them
+        let square = Polygon::new(vec![(2.0, 2.0), (4.0, 2.0), (4.0, 4.0), (2.0, 4.0)]);
+        assert!(!square.crosses(&Rectangle::new(2, 2, 3, 3)));
+        assert!(!square.crosses(&Rectangle::new(4, 0, 5, 5)));
+        assert!(square.crosses(&Rectangle::new(3, 3, 4, 4)));
+    }
+}
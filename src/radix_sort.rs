@@ -0,0 +1,207 @@
+use std::thread;
+
+const RADIX_BITS: u32 = 8;
+const BUCKETS: usize = 1 << RADIX_BITS;
+
+/// Unsigned integer key that can be sorted one byte at a time.
+pub trait RadixKey: Copy {
+    const BITS: u32;
+
+    /// Byte of the key starting at the bit `shift`.
+    fn digit(self, shift: u32) -> usize;
+}
+
+impl RadixKey for u32 {
+    const BITS: u32 = u32::BITS;
+
+    fn digit(self, shift: u32) -> usize {
+        ((self >> shift) as usize) & (BUCKETS - 1)
+    }
+}
+
+impl RadixKey for u64 {
+    const BITS: u32 = u64::BITS;
+
+    fn digit(self, shift: u32) -> usize {
+        ((self >> shift) as usize) & (BUCKETS - 1)
+    }
+}
+
+/// Stable LSD radix sort returning the permutation: `keys[result[0]]` is the smallest key.
+///
+/// Passes where every key has the same byte are skipped, so small 64-bit keys cost as much as
+/// 32-bit keys.
+pub fn radix_sort_permutation<K: RadixKey>(keys: &[K]) -> Vec<u32> {
+    if keys.len() > u32::MAX as usize {
+        panic!("The number of keys must fit in u32");
+    }
+    let mut permutation: Vec<u32> = (0..keys.len() as u32).collect();
+    let mut buffer = vec![0u32; keys.len()];
+    for pass in 0..(K::BITS / RADIX_BITS) {
+        let shift = pass * RADIX_BITS;
+        let mut counts = [0usize; BUCKETS];
+        for position in &permutation {
+            counts[keys[*position as usize].digit(shift)] += 1;
+        }
+        if counts.contains(&keys.len()) {
+            continue;
+        }
+        let mut offsets = [0usize; BUCKETS];
+        for bucket in 1..BUCKETS {
+            offsets[bucket] = offsets[bucket - 1] + counts[bucket - 1];
+        }
+        for position in &permutation {
+            let bucket = keys[*position as usize].digit(shift);
+            buffer[offsets[bucket]] = *position;
+            offsets[bucket] += 1;
+        }
+        std::mem::swap(&mut permutation, &mut buffer);
+    }
+    permutation
+}
+
+/// Stable LSD radix sort of `(key, payload)` pairs on the key, split over `threads` scoped threads.
+///
+/// Each pass counts the digits of the chunk of every thread, then every thread scatters its chunk
+/// into a single output buffer. A bucket holds the pairs of the first chunk, then of the second one
+/// and so on, which keeps the sort stable. Like `radix_sort_permutation`, passes where every key has
+/// the same byte are skipped.
+pub(crate) fn par_radix_sort_pairs<K>(mut pairs: Vec<(K, u32)>, threads: usize) -> Vec<(K, u32)>
+where
+    K: RadixKey + Send + Sync,
+{
+    if pairs.len() < 2 {
+        return pairs;
+    }
+    let chunk_size = pairs.len().div_ceil(threads);
+    let mut buffer = pairs.clone();
+    for pass in 0..(K::BITS / RADIX_BITS) {
+        let shift = pass * RADIX_BITS;
+        let chunk_counts: Vec<[usize; BUCKETS]> = thread::scope(|scope| {
+            let handles: Vec<_> = pairs
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut counts = [0usize; BUCKETS];
+                        for pair in chunk {
+                            counts[pair.0.digit(shift)] += 1;
+                        }
+                        counts
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        let constant_byte = (0..BUCKETS).any(|bucket| {
+            chunk_counts
+                .iter()
+                .map(|counts| counts[bucket])
+                .sum::<usize>()
+                == pairs.len()
+        });
+        if constant_byte {
+            continue;
+        }
+
+        // Split the output in one slice per bucket and chunk, bucket by bucket
+        let mut targets: Vec<Vec<&mut [(K, u32)]>> = chunk_counts
+            .iter()
+            .map(|_| Vec::with_capacity(BUCKETS))
+            .collect();
+        let mut remaining: &mut [(K, u32)] = &mut buffer;
+        for bucket in 0..BUCKETS {
+            for (counts, chunk_targets) in chunk_counts.iter().zip(targets.iter_mut()) {
+                let (target, rest) = std::mem::take(&mut remaining).split_at_mut(counts[bucket]);
+                remaining = rest;
+                chunk_targets.push(target);
+            }
+        }
+
+        thread::scope(|scope| {
+            for (chunk, mut chunk_targets) in pairs.chunks(chunk_size).zip(targets) {
+                scope.spawn(move || {
+                    let mut filled = [0usize; BUCKETS];
+                    for pair in chunk {
+                        let bucket = pair.0.digit(shift);
+                        chunk_targets[bucket][filled[bucket]] = *pair;
+                        filled[bucket] += 1;
+                    }
+                });
+            }
+        });
+        std::mem::swap(&mut pairs, &mut buffer);
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod test_par_radix_sort_pairs {
+    use super::*;
+
+    #[test]
+    fn test_par_radix_sort_pairs_is_stable() {
+        let pairs: Vec<(u32, u32)> = (0..1000u32)
+            .map(|payload| ((payload * 7919) % 13 * 0x01010101, payload))
+            .collect();
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|pair| pair.0);
+        for threads in 1..6 {
+            assert_eq!(expected, par_radix_sort_pairs(pairs.clone(), threads));
+        }
+    }
+
+    #[test]
+    fn test_par_radix_sort_pairs_skips_constant_bytes() {
+        // Only the second byte varies, the other passes are skipped
+        let pairs: Vec<(u64, u32)> = (0..300u32)
+            .map(|payload| {
+                (
+                    0xAB00_0000_0000_0000 | u64::from((payload * 37) % 256) << 8,
+                    payload,
+                )
+            })
+            .collect();
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|pair| pair.0);
+        for threads in [1, 3, 7] {
+            assert_eq!(expected, par_radix_sort_pairs(pairs.clone(), threads));
+        }
+    }
+
+    #[test]
+    fn test_par_radix_sort_pairs_small_inputs() {
+        assert_eq!(
+            Vec::<(u32, u32)>::new(),
+            par_radix_sort_pairs(Vec::<(u32, u32)>::new(), 4)
+        );
+        assert_eq!(
+            vec![(1, 0), (2, 1)],
+            par_radix_sort_pairs(vec![(2u32, 1), (1, 0)], 8)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_radix_sort_permutation {
+    use super::*;
+
+    #[test]
+    fn test_radix_sort_permutation_u32_is_stable() {
+        let keys = [0x0300_0001u32, 5, 0x0300_0001, 2, 5];
+        assert_eq!(vec![3, 1, 4, 0, 2], radix_sort_permutation(&keys));
+    }
+
+    #[test]
+    fn test_radix_sort_permutation_u64_high_bytes() {
+        let keys = [1u64 << 63, 1u64 << 40, 7, 1u64 << 40];
+        assert_eq!(vec![2, 1, 3, 0], radix_sort_permutation(&keys));
+    }
+
+    #[test]
+    fn test_radix_sort_permutation_empty() {
+        assert!(radix_sort_permutation::<u32>(&[]).is_empty());
+    }
+}
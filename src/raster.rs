@@ -0,0 +1,203 @@
+//! Binary PPM (P6) and PGM (P5) rendering of the curve and of values laid out along it.
+use crate::color::{Colormap, Rgb};
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RasterImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb>,
+}
+
+impl RasterImage {
+    pub fn new(width: u32, height: u32, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Pixels in row-major order, (0, 0) being the top-left corner.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Rgb {
+        self.pixels[self.pixel_position(x, y)]
+    }
+
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Rgb) {
+        let position = self.pixel_position(x, y);
+        self.pixels[position] = color;
+    }
+
+    pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+        let x_end = x.saturating_add(width).min(self.width);
+        let y_end = y.saturating_add(height).min(self.height);
+        for row in y..y_end {
+            for column in x..x_end {
+                self.set_pixel(column, row, color);
+            }
+        }
+    }
+
+    /// Draw a one pixel wide line with the Bresenham algorithm. Pixels outside the image are skipped.
+    pub fn draw_line(&mut self, from: CoordinateValue, to: CoordinateValue, color: Rgb) {
+        let (mut x, mut y) = (from.x as i64, from.y as i64);
+        let (x_end, y_end) = (to.x as i64, to.y as i64);
+        let dx = (x_end - x).abs();
+        let dy = -(y_end - y).abs();
+        let step_x = if x < x_end { 1 } else { -1 };
+        let step_y = if y < y_end { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            if x < self.width as i64 && y < self.height as i64 {
+                self.set_pixel(x as u32, y as u32, color);
+            }
+            if x == x_end && y == y_end {
+                break;
+            }
+            let double_error = 2 * error;
+            if double_error >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if double_error <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Encode the image as a binary PPM (P6).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.reserve(self.pixels.len() * 3);
+        for pixel in &self.pixels {
+            bytes.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+        bytes
+    }
+
+    /// Encode the image as a binary PGM (P5) using the Rec. 601 luma of each pixel.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut bytes = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().map(luma));
+        bytes
+    }
+
+    fn pixel_position(&self, x: u32, y: u32) -> usize {
+        if x >= self.width || y >= self.height {
+            panic!("The pixel must be inside the image");
+        }
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+fn luma(pixel: &Rgb) -> u8 {
+    (0.299 * pixel.r as f64 + 0.587 * pixel.g as f64 + 0.114 * pixel.b as f64).round() as u8
+}
+
+/// Draw the path of the curve, each cell being `cell_size` pixels wide.
+pub fn render_curve(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    cell_size: u32,
+    stroke: Rgb,
+    background: Rgb,
+) -> RasterImage {
+    let total = number_of_cells(hilbert_curve);
+    let width = image_width(hilbert_curve, cell_size);
+    let mut image = RasterImage::new(width, width, background);
+    let center = |point: CoordinateValue| CoordinateValue {
+        x: point.x * cell_size + cell_size / 2,
+        y: point.y * cell_size + cell_size / 2,
+    };
+    let mut previous = center(hilbert_curve.index_to_point(0));
+    image.set_pixel(previous.x, previous.y, stroke);
+    for index in 1..total {
+        let current = center(hilbert_curve.index_to_point(index));
+        image.draw_line(previous, current, stroke);
+        previous = current;
+    }
+    image
+}
+
+/// Color each cell from `values`, where `values[i]` belongs to the cell at Hilbert index `i`.
+///
+/// Values are normalized between the minimum and maximum finite values. Cells without a value
+/// (or with a non-finite value) keep the `background` color.
+pub fn render_values(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    values: &[f64],
+    colormap: Colormap,
+    cell_size: u32,
+    background: Rgb,
+) -> RasterImage {
+    let total = number_of_cells(hilbert_curve);
+    if values.len() as u64 > u64::from(total) {
+        panic!("There are more values than cells in the curve. Increase the order.");
+    }
+    let width = image_width(hilbert_curve, cell_size);
+    let mut image = RasterImage::new(width, width, background);
+    let finite = values.iter().copied().filter(|value| value.is_finite());
+    let minimum = finite.clone().fold(f64::INFINITY, f64::min);
+    let maximum = finite.fold(f64::NEG_INFINITY, f64::max);
+    let span = maximum - minimum;
+    for (index, value) in values.iter().enumerate() {
+        if !value.is_finite() {
+            continue;
+        }
+        let t = if span > 0.0 {
+            (value - minimum) / span
+        } else {
+            1.0
+        };
+        let point = hilbert_curve.index_to_point(index as u32);
+        image.fill_rect(
+            point.x * cell_size,
+            point.y * cell_size,
+            cell_size,
+            cell_size,
+            colormap.color(t),
+        );
+    }
+    image
+}
+
+fn number_of_cells(hilbert_curve: &HilbertCurveAlgorithm) -> u32 {
+    let number_of_rows = u64::from(hilbert_curve.number_of_rows());
+    let total = number_of_rows * number_of_rows;
+    if total > u64::from(u32::MAX) {
+        panic!("The order is too high to render every cell of the curve");
+    }
+    total as u32
+}
+
+fn image_width(hilbert_curve: &HilbertCurveAlgorithm, cell_size: u32) -> u32 {
+    if cell_size == 0 {
+        panic!("The cell size must be at least one pixel");
+    }
+    hilbert_curve.number_of_rows() * cell_size
+}
+
+#[cfg(test)]
+mod test_luma {
+    use super::*;
+
+    #[test]
+    fn test_luma_extremes() {
+        assert_eq!(0, luma(&Rgb::BLACK));
+        assert_eq!(255, luma(&Rgb::WHITE));
+        assert_eq!(76, luma(&Rgb::new(255, 0, 0)));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::coordinate_value::CoordinateValue;
+
+/// Axis-aligned rectangle of cells. Both the minimum and the maximum coordinates are included.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rectangle {
+    pub x_min: u32,
+    pub y_min: u32,
+    pub x_max: u32,
+    pub y_max: u32,
+}
+
+impl Rectangle {
+    pub fn new(x_min: u32, y_min: u32, x_max: u32, y_max: u32) -> Self {
+        if x_min > x_max || y_min > y_max {
+            panic!("The minimum coordinates must be lower or equal to the maximum coordinates");
+        }
+        Self {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        }
+    }
+
+    pub fn from_point(point: CoordinateValue) -> Self {
+        Self::new(point.x, point.y, point.x, point.y)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.x_max - self.x_min + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.y_max - self.y_min + 1
+    }
+
+    pub fn area(&self) -> u64 {
+        u64::from(self.width()) * u64::from(self.height())
+    }
+
+    pub fn contains(&self, point: CoordinateValue) -> bool {
+        point.x >= self.x_min
+            && point.x <= self.x_max
+            && point.y >= self.y_min
+            && point.y <= self.y_max
+    }
+
+    pub fn contains_rectangle(&self, other: &Rectangle) -> bool {
+        other.x_min >= self.x_min
+            && other.x_max <= self.x_max
+            && other.y_min >= self.y_min
+            && other.y_max <= self.y_max
+    }
+
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.x_min <= other.x_max
+            && other.x_min <= self.x_max
+            && self.y_min <= other.y_max
+            && other.y_min <= self.y_max
+    }
+
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rectangle::new(
+            self.x_min.max(other.x_min),
+            self.y_min.max(other.y_min),
+            self.x_max.min(other.x_max),
+            self.y_max.min(other.y_max),
+        ))
+    }
+
+    /// Smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        Rectangle::new(
+            self.x_min.min(other.x_min),
+            self.y_min.min(other.y_min),
+            self.x_max.max(other.x_max),
+            self.y_max.max(other.y_max),
+        )
+    }
+}
@@ -0,0 +1,229 @@
+use crate::hilbert_cell::{HilbertCell, MAXIMUM_CELL_ORDER};
+use crate::hilbert_region::HilbertRegion;
+use crate::polygon::Polygon;
+use crate::rectangle::Rectangle;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::RangeInclusive;
+
+/// Position of a block of cells relative to a region.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlockRelation {
+    Inside,
+    Outside,
+    Partial,
+}
+
+/// Region that can tell whether a block of cells is inside it, outside of it or both.
+pub trait CoverableRegion {
+    fn relation(&self, block: &Rectangle) -> BlockRelation;
+
+    /// Relation of a cell measured on the grid of `grid_order`.
+    fn cell_relation(&self, cell: &HilbertCell, grid_order: u16) -> BlockRelation {
+        self.relation(&cell.bounds(grid_order))
+    }
+}
+
+impl CoverableRegion for Rectangle {
+    fn relation(&self, block: &Rectangle) -> BlockRelation {
+        if self.contains_rectangle(block) {
+            BlockRelation::Inside
+        } else if self.intersects(block) {
+            BlockRelation::Partial
+        } else {
+            BlockRelation::Outside
+        }
+    }
+}
+
+impl CoverableRegion for Polygon {
+    fn relation(&self, block: &Rectangle) -> BlockRelation {
+        if self.crosses(block) {
+            return BlockRelation::Partial;
+        }
+        // No edge goes through the block: its center tells for the whole block
+        let x = (f64::from(block.x_min) + f64::from(block.x_max) + 1.0) / 2.0;
+        let y = (f64::from(block.y_min) + f64::from(block.y_max) + 1.0) / 2.0;
+        if self.contains(x, y) {
+            BlockRelation::Inside
+        } else {
+            BlockRelation::Outside
+        }
+    }
+}
+
+impl CoverableRegion for HilbertRegion {
+    /// The block is measured on the grid of the order of the region.
+    fn relation(&self, block: &Rectangle) -> BlockRelation {
+        let covered = self
+            .hilbert_curve()
+            .ranges_for_rectangle(block)
+            .iter()
+            .map(|block_range| covered_cells(self.ranges(), block_range))
+            .sum();
+        relation_from_coverage(covered, block.area())
+    }
+
+    /// The cell is compared with the region at the order of the region, whatever the grid order.
+    fn cell_relation(&self, cell: &HilbertCell, _grid_order: u16) -> BlockRelation {
+        let order = self.order();
+        let cell_range = if cell.order() <= order {
+            cell.index_range(order)
+        } else {
+            // Finer than the region: the cell lies inside one cell of the region
+            let shift = 2 * u32::from(cell.order() - order);
+            let ancestor = (u64::from(cell.index()) >> shift) as u32;
+            ancestor..=ancestor
+        };
+        let length = u64::from(cell_range.end() - cell_range.start()) + 1;
+        relation_from_coverage(covered_cells(self.ranges(), &cell_range), length)
+    }
+}
+
+/// Number of indices of `block_range` inside the sorted disjoint `ranges`.
+fn covered_cells(ranges: &[RangeInclusive<u32>], block_range: &RangeInclusive<u32>) -> u64 {
+    let first = ranges.partition_point(|range| range.end() < block_range.start());
+    ranges[first..]
+        .iter()
+        .take_while(|range| range.start() <= block_range.end())
+        .map(|range| {
+            let start = *range.start().max(block_range.start());
+            let end = *range.end().min(block_range.end());
+            u64::from(end - start) + 1
+        })
+        .sum()
+}
+
+fn relation_from_coverage(covered: u64, area: u64) -> BlockRelation {
+    if covered == 0 {
+        BlockRelation::Outside
+    } else if covered == area {
+        BlockRelation::Inside
+    } else {
+        BlockRelation::Partial
+    }
+}
+
+/// Approximation of a region with cells of mixed orders.
+///
+/// The region is measured on the grid of `grid_order`, except a `HilbertRegion` which is measured on
+/// the grid of its own order. The largest cells are split first, as long as
+/// the number of cells stays within `max_cells`. Cells of an order below `min_order` are always split,
+/// which can exceed `max_cells`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegionCoverer {
+    pub grid_order: u16,
+    pub min_order: u16,
+    pub max_order: u16,
+    pub max_cells: usize,
+}
+
+impl Default for RegionCoverer {
+    fn default() -> Self {
+        Self {
+            grid_order: MAXIMUM_CELL_ORDER,
+            min_order: 0,
+            max_order: MAXIMUM_CELL_ORDER,
+            max_cells: 8,
+        }
+    }
+}
+
+impl RegionCoverer {
+    /// Sorted cells holding every cell of the region.
+    pub fn covering<R: CoverableRegion>(&self, region: &R) -> Vec<HilbertCell> {
+        self.cover(region, false)
+    }
+
+    /// Sorted cells inside the region.
+    pub fn interior_covering<R: CoverableRegion>(&self, region: &R) -> Vec<HilbertCell> {
+        self.cover(region, true)
+    }
+
+    fn cover<R: CoverableRegion>(&self, region: &R, interior: bool) -> Vec<HilbertCell> {
+        if self.grid_order > MAXIMUM_CELL_ORDER || self.max_order > self.grid_order {
+            panic!("The maximum order must be at most the grid order, which must be 16 or less");
+        }
+        if self.min_order > self.max_order {
+            panic!("The minimum order must be at most the maximum order");
+        }
+        if self.max_cells == 0 {
+            panic!("The maximum number of cells must be at least one");
+        }
+        let mut cells = Vec::new();
+        let mut candidates = BinaryHeap::new();
+        let root = HilbertCell::root();
+        match region.cell_relation(&root, self.grid_order) {
+            BlockRelation::Outside => return cells,
+            relation => candidates.push(Reverse((0, root, relation == BlockRelation::Inside))),
+        }
+        // Largest cells first, then in curve order
+        while let Some(Reverse((_, cell, inside))) = candidates.pop() {
+            let forced = cell.order() < self.min_order;
+            if inside && !forced {
+                cells.push(cell);
+                continue;
+            }
+            if cell.order() == self.max_order {
+                if !interior {
+                    cells.push(cell);
+                }
+                continue;
+            }
+            let children: Vec<(HilbertCell, BlockRelation)> = cell
+                .children()
+                .into_iter()
+                .map(|child| (child, region.cell_relation(&child, self.grid_order)))
+                .filter(|(_, relation)| *relation != BlockRelation::Outside)
+                .collect();
+            if forced || cells.len() + candidates.len() + children.len() <= self.max_cells {
+                candidates.extend(children.into_iter().map(|(child, relation)| {
+                    Reverse((child.order(), child, relation == BlockRelation::Inside))
+                }));
+            } else if !interior {
+                cells.push(cell);
+            }
+        }
+        normalize(cells, self.min_order)
+    }
+}
+
+/// Sort the cells and replace every four siblings by their parent, when the parent order is allowed.
+fn normalize(mut cells: Vec<HilbertCell>, min_order: u16) -> Vec<HilbertCell> {
+    cells.sort();
+    let mut normalized: Vec<HilbertCell> = Vec::with_capacity(cells.len());
+    for cell in cells {
+        normalized.push(cell);
+        while normalized.len() >= 4 {
+            let last = normalized[normalized.len() - 1];
+            let Some(parent) = last.parent().filter(|parent| parent.order() >= min_order) else {
+                break;
+            };
+            if normalized[normalized.len() - 4..] != parent.children() {
+                break;
+            }
+            normalized.truncate(normalized.len() - 4);
+            normalized.push(parent);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test_normalize {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let parent = HilbertCell::new(2, 5);
+        let mut cells = parent.children()[1].children().to_vec();
+        cells.extend([
+            parent.children()[3],
+            parent.children()[0],
+            parent.children()[2],
+        ]);
+        assert_eq!(vec![parent], normalize(cells.clone(), 0));
+        assert_eq!(vec![parent], normalize(cells.clone(), 2));
+        assert_eq!(parent.children().to_vec(), normalize(cells, 3));
+    }
+}
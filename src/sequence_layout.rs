@@ -0,0 +1,108 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    Max,
+}
+
+/// A 1D sequence folded on a Hilbert square (HilbertVis), each cell holding a bin of consecutive samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HilbertLayout {
+    order: u16,
+    samples_per_cell: usize,
+    sample_count: usize,
+    grid: Vec<Option<f64>>,
+}
+
+impl HilbertLayout {
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    pub fn number_of_rows(&self) -> u32 {
+        u32::pow(2, self.order.into())
+    }
+
+    pub fn samples_per_cell(&self) -> usize {
+        self.samples_per_cell
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Aggregated values in row-major order, `None` for the cells after the end of the sequence.
+    pub fn grid(&self) -> &[Option<f64>] {
+        &self.grid
+    }
+
+    pub fn value(&self, point: CoordinateValue) -> Option<f64> {
+        self.grid[self.grid_position(point)]
+    }
+
+    /// Range of the original samples aggregated in the cell, `None` if the cell is empty.
+    pub fn sample_range(&self, point: CoordinateValue) -> Option<Range<usize>> {
+        let index = HilbertCurveAlgorithm::new(self.order).point_to_index(point) as usize;
+        let start = index.checked_mul(self.samples_per_cell)?;
+        if start >= self.sample_count {
+            return None;
+        }
+        Some(start..(start + self.samples_per_cell).min(self.sample_count))
+    }
+
+    pub fn sample_to_point(&self, sample: usize) -> CoordinateValue {
+        if sample >= self.sample_count {
+            panic!("The sample is outside the sequence");
+        }
+        HilbertCurveAlgorithm::new(self.order)
+            .index_to_point((sample / self.samples_per_cell) as u32)
+    }
+
+    fn grid_position(&self, point: CoordinateValue) -> usize {
+        let number_of_rows = self.number_of_rows();
+        if point.x >= number_of_rows || point.y >= number_of_rows {
+            panic!("The point must be in range with the order");
+        }
+        point.y as usize * number_of_rows as usize + point.x as usize
+    }
+}
+
+/// Fold `data` on the smallest curve that holds one sample per cell, without going above `max_order`.
+///
+/// When the sequence is longer than the `max_order` curve, consecutive samples are binned and
+/// aggregated so that every cell holds the same number of samples (except the last non-empty one).
+pub fn layout_sequence(data: &[f64], max_order: u16, aggregation: Aggregation) -> HilbertLayout {
+    if max_order > 15 {
+        panic!("The maximum order must be 15 or less to fit the grid in memory");
+    }
+    let mut order = 0;
+    while order < max_order && (1usize << (2 * order)) < data.len() {
+        order += 1;
+    }
+    let cells = 1usize << (2 * order);
+    let samples_per_cell = data.len().div_ceil(cells).max(1);
+
+    let hilbert_curve = HilbertCurveAlgorithm::new(order);
+    let number_of_rows = hilbert_curve.number_of_rows() as usize;
+    let mut grid = vec![None; cells];
+    for (index, bin) in data.chunks(samples_per_cell).enumerate() {
+        let value = match aggregation {
+            Aggregation::Sum => bin.iter().sum(),
+            Aggregation::Mean => bin.iter().sum::<f64>() / bin.len() as f64,
+            Aggregation::Max => bin.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        };
+        let point = hilbert_curve.index_to_point(index as u32);
+        grid[point.y as usize * number_of_rows + point.x as usize] = Some(value);
+    }
+
+    HilbertLayout {
+        order,
+        samples_per_cell,
+        sample_count: data.len(),
+        grid,
+    }
+}
@@ -0,0 +1,238 @@
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::{coarsen_ranges, HilbertCurveAlgorithm};
+use crate::rectangle::Rectangle;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::RangeInclusive;
+
+/// Maximum number of index ranges describing the query region of one item.
+const JOIN_RANGE_BUDGET: usize = 8;
+
+/// Index range of an item: start, end and position of the item in its input.
+type Probe = (u32, u32, usize);
+
+/// Range of a box waiting for its start: start, side (0 for left), end and position of the box.
+type RangeEvent = (u32, usize, u32, usize);
+
+/// Call `on_pair(left_position, right_position)` for every pair of points at most `distance` apart.
+///
+/// Both inputs hold `(Hilbert index, point)` pairs of the curve sorted by index, and are merged in a
+/// single forward pass. Each point read becomes the index ranges of the cells near it, from its own
+/// index on. A point is compared with the points of the other input whose ranges contain its index,
+/// so only the ranges of the points near the current index are kept.
+pub fn join_within_distance<F: FnMut(usize, usize)>(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    left: &[(u32, CoordinateValue)],
+    right: &[(u32, CoordinateValue)],
+    distance: u32,
+    mut on_pair: F,
+) {
+    for points in [left, right] {
+        if points.windows(2).any(|pair| pair[0].0 > pair[1].0) {
+            panic!("The points must be sorted by Hilbert index");
+        }
+    }
+    let squared_distance = u64::from(distance) * u64::from(distance);
+    // A point within the distance has its cell center within the distance of the center of this
+    // cell, so its cell overlaps a slightly larger disk
+    let radius = f64::from(distance) + 0.5;
+    let mut queues = [ProbeQueue::default(), ProbeQueue::default()];
+    let (mut next_left, mut next_right) = (0, 0);
+    while next_left < left.len() || next_right < right.len() {
+        // On equal indices the left point goes first, so the right one finds its ranges
+        let take_left = next_right == right.len()
+            || (next_left < left.len() && left[next_left].0 <= right[next_right].0);
+        let (side, position, (index, point)) = if take_left {
+            next_left += 1;
+            (0, next_left - 1, left[next_left - 1])
+        } else {
+            next_right += 1;
+            (1, next_right - 1, right[next_right - 1])
+        };
+        let others = &mut queues[1 - side];
+        others.advance_to(index);
+        for other_position in others.positions() {
+            let other = if take_left {
+                right[other_position].1
+            } else {
+                left[other_position].1
+            };
+            let dx = u64::from(other.x.abs_diff(point.x));
+            let dy = u64::from(other.y.abs_diff(point.y));
+            if dx * dx + dy * dy <= squared_distance {
+                if take_left {
+                    on_pair(position, other_position);
+                } else {
+                    on_pair(other_position, position);
+                }
+            }
+        }
+        // The points read later all have an index at or after this one
+        let center = (f64::from(point.x) + 0.5, f64::from(point.y) + 0.5);
+        for range in hilbert_curve.ranges_for_circle(center, radius, Some(JOIN_RANGE_BUDGET)) {
+            if *range.end() >= index {
+                queues[side].push((*range.start().max(&index), *range.end(), position));
+            }
+        }
+    }
+}
+
+/// Call `on_pair(left_position, right_position)` for every pair of intersecting boxes.
+///
+/// Both inputs must be sorted by the first Hilbert index of their cells, which is
+/// `hilbert_curve.ranges_for_rectangle(&rectangle)[0].start()`, and lie inside the grid of the curve.
+/// Every box read becomes the index ranges of its cells, and the ranges of both inputs are merged in a
+/// single forward pass. A pair is reported once, by the ranges holding the first cell of the
+/// intersection of the boxes.
+pub fn join_intersecting<F: FnMut(usize, usize)>(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    left: &[Rectangle],
+    right: &[Rectangle],
+    mut on_pair: F,
+) {
+    let mut cursors = [BoxCursor::new(left), BoxCursor::new(right)];
+    let mut events: BinaryHeap<Reverse<RangeEvent>> = BinaryHeap::new();
+    let mut active: [BinaryHeap<Reverse<Probe>>; 2] = [BinaryHeap::new(), BinaryHeap::new()];
+    loop {
+        // A box is read before the ranges starting at its first index
+        let event_start = events.peek().map(|Reverse((start, _, _, _))| *start);
+        let next_box = match (
+            cursors[0].peek_start(hilbert_curve),
+            cursors[1].peek_start(hilbert_curve),
+        ) {
+            (Some(left_start), Some(right_start)) if right_start < left_start => {
+                Some((1, right_start))
+            }
+            (Some(left_start), _) => Some((0, left_start)),
+            (None, right_start) => right_start.map(|start| (1, start)),
+        };
+        if let Some((side, box_start)) = next_box {
+            if event_start.is_none_or(|start| box_start <= start) {
+                let (position, ranges) = cursors[side].take();
+                events.extend(
+                    ranges
+                        .into_iter()
+                        .map(|range| Reverse((*range.start(), side, *range.end(), position))),
+                );
+                continue;
+            }
+        }
+        // On equal starts the left range goes first, so the right one finds it active
+        let Some(Reverse((start, side, end, position))) = events.pop() else {
+            break;
+        };
+        let others = &mut active[1 - side];
+        while others
+            .peek()
+            .is_some_and(|Reverse((other_end, _, _))| *other_end < start)
+        {
+            others.pop();
+        }
+        for Reverse((other_end, _, other)) in others.iter() {
+            let (left_position, right_position) = if side == 0 {
+                (position, *other)
+            } else {
+                (*other, position)
+            };
+            let Some(intersection) = left[left_position].intersection(&right[right_position])
+            else {
+                continue;
+            };
+            // The ranges of both boxes overlap on `start..=end.min(other_end)`
+            let first = first_index(hilbert_curve, &intersection);
+            if start <= first && first <= end.min(*other_end) {
+                on_pair(left_position, right_position);
+            }
+        }
+        active[side].push(Reverse((end, start, position)));
+    }
+}
+
+/// Ranges of the points read so far, made active once the merge reaches their start.
+#[derive(Default)]
+struct ProbeQueue {
+    pending: BinaryHeap<Reverse<Probe>>,
+    /// Probes containing the current index, keyed by their end.
+    active: BinaryHeap<Reverse<Probe>>,
+}
+
+impl ProbeQueue {
+    fn push(&mut self, probe: Probe) {
+        self.pending.push(Reverse(probe));
+    }
+
+    /// Keep the probes containing `index`. The indices must not decrease between calls.
+    fn advance_to(&mut self, index: u32) {
+        while self
+            .pending
+            .peek()
+            .is_some_and(|Reverse((start, _, _))| *start <= index)
+        {
+            let Reverse((start, end, position)) = self.pending.pop().unwrap();
+            self.active.push(Reverse((end, start, position)));
+        }
+        while self
+            .active
+            .peek()
+            .is_some_and(|Reverse((end, _, _))| *end < index)
+        {
+            self.active.pop();
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.active
+            .iter()
+            .map(|Reverse((_, _, position))| *position)
+    }
+}
+
+/// Boxes of one input, read in order with the index ranges of the next box.
+struct BoxCursor<'a> {
+    boxes: &'a [Rectangle],
+    next: usize,
+    ranges: Option<Vec<RangeInclusive<u32>>>,
+    previous_start: u32,
+}
+
+impl<'a> BoxCursor<'a> {
+    fn new(boxes: &'a [Rectangle]) -> Self {
+        Self {
+            boxes,
+            next: 0,
+            ranges: None,
+            previous_start: 0,
+        }
+    }
+
+    /// First index of the next box.
+    fn peek_start(&mut self, hilbert_curve: &HilbertCurveAlgorithm) -> Option<u32> {
+        if self.ranges.is_none() {
+            let rectangle = self.boxes.get(self.next)?;
+            let last = hilbert_curve.number_of_rows() - 1;
+            if rectangle.x_max > last || rectangle.y_max > last {
+                panic!("The boxes must lie inside the grid of the curve");
+            }
+            let ranges = coarsen_ranges(
+                hilbert_curve.ranges_for_rectangle(rectangle),
+                Some(JOIN_RANGE_BUDGET),
+            );
+            if *ranges[0].start() < self.previous_start {
+                panic!("The boxes must be sorted by the first Hilbert index of their cells");
+            }
+            self.previous_start = *ranges[0].start();
+            self.ranges = Some(ranges);
+        }
+        Some(self.previous_start)
+    }
+
+    /// Position and ranges of the box seen by `peek_start`.
+    fn take(&mut self) -> (usize, Vec<RangeInclusive<u32>>) {
+        self.next += 1;
+        (self.next - 1, self.ranges.take().unwrap())
+    }
+}
+
+fn first_index(hilbert_curve: &HilbertCurveAlgorithm, rectangle: &Rectangle) -> u32 {
+    *hilbert_curve.ranges_for_rectangle(rectangle)[0].start()
+}
@@ -0,0 +1,199 @@
+use crate::color::Rgb;
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+#[derive(Clone)]
+pub struct SvgOptions {
+    /// Width and height of the SVG in pixels, margin included.
+    pub size: u32,
+    pub margin: u32,
+    pub stroke: Rgb,
+    pub stroke_width: f64,
+    pub background: Option<Rgb>,
+    /// Color the path from the first color (index 0) to the second color (last index).
+    pub gradient: Option<(Rgb, Rgb)>,
+    /// Draw the cell boundaries with the given color.
+    pub grid: Option<Rgb>,
+    /// Fill the cells and the path of the given inclusive index range.
+    pub highlight: Option<RangeInclusive<u32>>,
+    pub highlight_color: Rgb,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            size: 512,
+            margin: 16,
+            stroke: Rgb::BLACK,
+            stroke_width: 2.0,
+            background: Some(Rgb::WHITE),
+            gradient: None,
+            grid: None,
+            highlight: None,
+            highlight_color: Rgb::new(230, 57, 70),
+        }
+    }
+}
+
+/// Render the path of the curve as a standalone SVG document.
+///
+/// The cell (0, 0) is drawn at the top-left corner, the same way `offset_point` projects points.
+pub fn render_svg(hilbert_curve: &HilbertCurveAlgorithm, options: SvgOptions) -> String {
+    let number_of_rows = hilbert_curve.number_of_rows();
+    let total = u64::from(number_of_rows) * u64::from(number_of_rows);
+    if total > u64::from(u32::MAX) {
+        panic!("The order is too high to render the curve as SVG");
+    }
+    let drawing_width = options.size.saturating_sub(2 * options.margin) as f64;
+    let cell_size = drawing_width / number_of_rows as f64;
+    let origin = options.margin as f64;
+    let center = |point: CoordinateValue| {
+        (
+            origin + (point.x as f64 + 0.5) * cell_size,
+            origin + (point.y as f64 + 0.5) * cell_size,
+        )
+    };
+    let points: Vec<(f64, f64)> = (0..total as u32)
+        .map(|index| center(hilbert_curve.index_to_point(index)))
+        .collect();
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#,
+        size = options.size
+    );
+    if let Some(background) = options.background {
+        let _ = writeln!(
+            svg,
+            r#"<rect x="0" y="0" width="{size}" height="{size}" fill="{}"/>"#,
+            background.to_hex(),
+            size = options.size
+        );
+    }
+
+    if let Some(grid) = options.grid {
+        let _ = writeln!(
+            svg,
+            r#"<g stroke="{}" stroke-width="1" fill="none">"#,
+            grid.to_hex()
+        );
+        let end = fmt_number(origin + drawing_width);
+        for row in 0..=number_of_rows {
+            let position = fmt_number(origin + row as f64 * cell_size);
+            let start = fmt_number(origin);
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{start}" y1="{position}" x2="{end}" y2="{position}"/>"#
+            );
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{position}" y1="{start}" x2="{position}" y2="{end}"/>"#
+            );
+        }
+        svg.push_str("</g>\n");
+    }
+
+    if let Some(highlight) = &options.highlight {
+        let _ = writeln!(
+            svg,
+            r#"<g fill="{}" fill-opacity="0.3">"#,
+            options.highlight_color.to_hex()
+        );
+        let size = fmt_number(cell_size);
+        for index in clamp_range(highlight, total) {
+            let point = hilbert_curve.index_to_point(index);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}"/>"#,
+                fmt_number(origin + point.x as f64 * cell_size),
+                fmt_number(origin + point.y as f64 * cell_size),
+            );
+        }
+        svg.push_str("</g>\n");
+    }
+
+    let stroke_width = fmt_number(options.stroke_width);
+    match options.gradient {
+        Some((start, end)) => {
+            let _ = writeln!(
+                svg,
+                r#"<g stroke-width="{stroke_width}" stroke-linecap="round">"#
+            );
+            let last = points.len().saturating_sub(2).max(1) as f64;
+            for (index, segment) in points.windows(2).enumerate() {
+                let color = start.lerp(end, index as f64 / last);
+                push_line(&mut svg, segment[0], segment[1], &color.to_hex());
+            }
+            svg.push_str("</g>\n");
+        }
+        None => {
+            push_polyline(&mut svg, &points, &options.stroke.to_hex(), &stroke_width);
+        }
+    }
+
+    if let Some(highlight) = &options.highlight {
+        let range = clamp_range(highlight, total);
+        if !range.is_empty() {
+            let selected = &points[*range.start() as usize..=*range.end() as usize];
+            let width = fmt_number(options.stroke_width * 1.5);
+            push_polyline(
+                &mut svg,
+                selected,
+                &options.highlight_color.to_hex(),
+                &width,
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn clamp_range(range: &RangeInclusive<u32>, total: u64) -> RangeInclusive<u32> {
+    let end = u64::from(*range.end()).min(total - 1) as u32;
+    *range.start()..=end
+}
+
+fn push_polyline(svg: &mut String, points: &[(f64, f64)], color: &str, stroke_width: &str) {
+    let coordinates: Vec<String> = points
+        .iter()
+        .map(|(x, y)| format!("{},{}", fmt_number(*x), fmt_number(*y)))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="{stroke_width}" stroke-linejoin="round" stroke-linecap="round"/>"#,
+        coordinates.join(" ")
+    );
+}
+
+fn push_line(svg: &mut String, from: (f64, f64), to: (f64, f64), color: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{color}"/>"#,
+        fmt_number(from.0),
+        fmt_number(from.1),
+        fmt_number(to.0),
+        fmt_number(to.1)
+    );
+}
+
+fn fmt_number(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    text.to_string()
+}
+
+#[cfg(test)]
+mod test_fmt_number {
+    use super::*;
+
+    #[test]
+    fn test_fmt_number_trims_zeros() {
+        assert_eq!("12", fmt_number(12.0));
+        assert_eq!("12.5", fmt_number(12.5));
+        assert_eq!("0.333", fmt_number(1.0 / 3.0));
+    }
+}
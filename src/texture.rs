@@ -0,0 +1,182 @@
+//! Swizzling of strided images into square tiles, the tiles being stored along a Hilbert curve.
+//!
+//! The swizzled buffer holds every tile, padded ones included, one after the other. The tiles follow the
+//! padded Hilbert scan of the grid of tiles and the pixels of a tile follow the selected in-tile order.
+use crate::coordinate_value::CoordinateValue;
+use crate::hilbert_curve_algorithm::HilbertCurveAlgorithm;
+use crate::image_scan::{for_each_scan_position, ScanMode};
+
+/// Order of the pixels inside a tile.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TileOrder {
+    RowMajor,
+    ColumnMajor,
+    Hilbert,
+}
+
+/// Value of the pixels of the tiles that fall outside of the image.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Padding<P> {
+    Constant(P),
+    /// Repeat the closest pixel of the image.
+    Clamp,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextureLayout {
+    width: u32,
+    height: u32,
+    tile_size: u32,
+    tile_order: TileOrder,
+    tiles_x: u32,
+    tiles_y: u32,
+    /// Row-major position of each tile in the tile grid, in storage order.
+    tile_positions: Vec<u32>,
+    /// Offset of each pixel inside a tile, in storage order.
+    pixel_offsets: Vec<CoordinateValue>,
+}
+
+impl TextureLayout {
+    pub fn new(width: u32, height: u32, tile_size: u32, tile_order: TileOrder) -> Self {
+        if !tile_size.is_power_of_two() {
+            panic!("The tile size must be a power of two");
+        }
+        let tiles_x = width.div_ceil(tile_size);
+        let tiles_y = height.div_ceil(tile_size);
+        let mut tile_positions = Vec::with_capacity(tiles_x as usize * tiles_y as usize);
+        for_each_scan_position(tiles_x, tiles_y, ScanMode::Padded, |position| {
+            tile_positions.push(position as u32)
+        });
+        let pixel_offsets = match tile_order {
+            TileOrder::RowMajor => (0..tile_size * tile_size)
+                .map(|offset| CoordinateValue {
+                    x: offset % tile_size,
+                    y: offset / tile_size,
+                })
+                .collect(),
+            TileOrder::ColumnMajor => (0..tile_size * tile_size)
+                .map(|offset| CoordinateValue {
+                    x: offset / tile_size,
+                    y: offset % tile_size,
+                })
+                .collect(),
+            TileOrder::Hilbert => HilbertCurveAlgorithm::new(tile_size.trailing_zeros() as u16)
+                .iter()
+                .collect(),
+        };
+        Self {
+            width,
+            height,
+            tile_size,
+            tile_order,
+            tiles_x,
+            tiles_y,
+            tile_positions,
+            pixel_offsets,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    pub fn tile_order(&self) -> TileOrder {
+        self.tile_order
+    }
+
+    /// Number of tiles per row and per column.
+    pub fn tile_grid(&self) -> (u32, u32) {
+        (self.tiles_x, self.tiles_y)
+    }
+
+    /// Number of pixels of the swizzled buffer, padding included.
+    pub fn swizzled_len(&self) -> usize {
+        self.tile_positions.len() * self.pixel_offsets.len()
+    }
+
+    /// Image pixel of each position of the swizzled buffer, `None` for padding.
+    pub fn swizzled_pixels(&self) -> impl Iterator<Item = Option<CoordinateValue>> + '_ {
+        self.tile_positions.iter().flat_map(move |tile| {
+            let x_min = (tile % self.tiles_x) * self.tile_size;
+            let y_min = (tile / self.tiles_x) * self.tile_size;
+            self.pixel_offsets.iter().map(move |offset| {
+                let point = CoordinateValue {
+                    x: x_min + offset.x,
+                    y: y_min + offset.y,
+                };
+                (point.x < self.width && point.y < self.height).then_some(point)
+            })
+        })
+    }
+
+    /// Tiles from an image whose rows start every `stride` pixels.
+    pub fn swizzle<P: Copy>(&self, source: &[P], stride: usize, padding: Padding<P>) -> Vec<P> {
+        self.check_strided_len(source.len(), stride);
+        let mut swizzled = Vec::with_capacity(self.swizzled_len());
+        for tile in &self.tile_positions {
+            let x_min = (tile % self.tiles_x) * self.tile_size;
+            let y_min = (tile / self.tiles_x) * self.tile_size;
+            for offset in &self.pixel_offsets {
+                let (x, y) = (x_min + offset.x, y_min + offset.y);
+                let pixel = if x < self.width && y < self.height {
+                    source[y as usize * stride + x as usize]
+                } else {
+                    match padding {
+                        Padding::Constant(pixel) => pixel,
+                        Padding::Clamp => {
+                            let x = x.min(self.width - 1) as usize;
+                            source[y.min(self.height - 1) as usize * stride + x]
+                        }
+                    }
+                };
+                swizzled.push(pixel);
+            }
+        }
+        swizzled
+    }
+
+    /// Write the image pixels of the tiles into a buffer whose rows start every `stride` pixels.
+    pub fn deswizzle_into<P: Copy>(&self, swizzled: &[P], destination: &mut [P], stride: usize) {
+        self.check_swizzled_len(swizzled.len());
+        self.check_strided_len(destination.len(), stride);
+        for (pixel, point) in swizzled.iter().zip(self.swizzled_pixels()) {
+            if let Some(point) = point {
+                destination[point.y as usize * stride + point.x as usize] = *pixel;
+            }
+        }
+    }
+
+    /// Row-major image, without stride, from the tiles.
+    pub fn deswizzle<P: Copy>(&self, swizzled: &[P]) -> Vec<P> {
+        self.check_swizzled_len(swizzled.len());
+        let Some(first) = swizzled.first() else {
+            return Vec::new();
+        };
+        let mut pixels = vec![*first; self.width as usize * self.height as usize];
+        self.deswizzle_into(swizzled, &mut pixels, self.width as usize);
+        pixels
+    }
+
+    fn check_swizzled_len(&self, length: usize) {
+        if length != self.swizzled_len() {
+            panic!("The swizzled buffer must have the length of the layout");
+        }
+    }
+
+    fn check_strided_len(&self, length: usize, stride: usize) {
+        if stride < self.width as usize {
+            panic!("The stride must be at least the width");
+        }
+        if self.height > 0 && length < (self.height as usize - 1) * stride + self.width as usize {
+            panic!("The buffer is too short for the height and the stride");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use hilbert_curve_rust::{render_ascii, render_ascii_indices, HilbertCurveAlgorithm};
+
+#[test]
+fn render_ascii_first_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    assert_eq!("╷ ╷\n└─┘\n", render_ascii(&hilbert_curve));
+}
+
+#[test]
+fn render_ascii_second_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let expected = "\
+╶─┐ ┌─╴
+┌─┘ └─┐
+│ ┌─┐ │
+└─┘ └─┘
+";
+    assert_eq!(expected, render_ascii(&hilbert_curve));
+}
+
+#[test]
+fn render_ascii_order_zero_is_a_single_cell() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(0);
+    assert_eq!("·\n", render_ascii(&hilbert_curve));
+}
+
+#[test]
+fn render_ascii_sixth_order_dimensions() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(6);
+    let text = render_ascii(&hilbert_curve);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(64, lines.len());
+    assert!(lines.iter().all(|line| line.chars().count() == 127));
+}
+
+#[test]
+#[should_panic(
+    expected = "The order is too high to render the curve as text. Use an order of 6 or less."
+)]
+fn render_ascii_seventh_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(7);
+    render_ascii(&hilbert_curve);
+}
+
+#[test]
+fn render_ascii_indices_first_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    assert_eq!("0 3\n1 2\n", render_ascii_indices(&hilbert_curve));
+}
+
+#[test]
+fn render_ascii_indices_second_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let expected = " 0  1 14 15
+ 3  2 13 12
+ 4  7  8 11
+ 5  6  9 10
+";
+    assert_eq!(expected, render_ascii_indices(&hilbert_curve));
+}
@@ -0,0 +1,48 @@
+use hilbert_curve_rust::{
+    BlockRelation, Circle, CoordinateValue, CoverableRegion, HilbertCell, HilbertRegion, Rectangle,
+    RegionCoverer,
+};
+
+#[test]
+fn contains() {
+    let circle = Circle::new((40.0, 30.0), 12.5);
+    assert_eq!((40.0, 30.0), circle.center());
+    assert_eq!(12.5, circle.radius());
+    assert!(circle.contains(50.0, 30.0));
+    assert!(!circle.contains(50.0, 40.0));
+}
+
+#[test]
+fn relation() {
+    let circle = Circle::new((40.0, 30.0), 12.5);
+    assert_eq!(
+        BlockRelation::Inside,
+        circle.relation(&Rectangle::new(36, 26, 43, 33))
+    );
+    assert_eq!(
+        BlockRelation::Outside,
+        circle.relation(&Rectangle::new(0, 0, 20, 20))
+    );
+    assert_eq!(
+        BlockRelation::Partial,
+        circle.relation(&Rectangle::new(30, 30, 60, 60))
+    );
+}
+
+#[test]
+fn covering() {
+    let circle = Circle::new((40.0, 30.0), 12.5);
+    let coverer = RegionCoverer {
+        grid_order: 6,
+        max_order: 6,
+        max_cells: 16,
+        ..RegionCoverer::default()
+    };
+    let region = |cells: Vec<HilbertCell>| {
+        HilbertRegion::from_ranges(6, cells.iter().map(|cell| cell.index_range(6)))
+    };
+    let covering = region(coverer.covering(&circle));
+    let interior = region(coverer.interior_covering(&circle));
+    assert!(interior.area() < covering.area());
+    assert!(covering.contains_point(CoordinateValue { x: 40, y: 42 }));
+}
@@ -0,0 +1,121 @@
+use hilbert_curve_rust::codec::{decode_gray, encode_gray, encode_gray_row_major};
+
+fn gradient(width: u32, height: u32) -> Vec<u8> {
+    (0..width * height)
+        .map(|position| ((position % width) * 3 + (position / width) * 5) as u8)
+        .collect()
+}
+
+#[test]
+fn round_trip() {
+    for (width, height) in [(1, 1), (16, 16), (37, 21), (5, 200), (0, 0)] {
+        let pixels = gradient(width, height);
+        let bytes = encode_gray(&pixels, width, height);
+        assert_eq!(Ok((pixels, width, height)), decode_gray(&bytes));
+    }
+}
+
+#[test]
+fn header() {
+    let bytes = encode_gray(&[7; 12], 4, 3);
+    assert_eq!(b"HGC1", &bytes[0..4]);
+    assert_eq!(4u32.to_le_bytes(), bytes[4..8]);
+    assert_eq!(3u32.to_le_bytes(), bytes[8..12]);
+    assert_eq!(2, bytes[12]);
+    // One difference of 7, then a run of 11 zero differences
+    assert_eq!(&[28, 21], &bytes[13..]);
+}
+
+#[test]
+fn uniform_image_is_one_run() {
+    let bytes = encode_gray(&[0; 256 * 256], 256, 256);
+    assert_eq!(13 + 3, bytes.len());
+}
+
+#[test]
+fn invalid_buffers() {
+    let bytes = encode_gray(&gradient(10, 10), 10, 10);
+    assert_eq!(
+        Err("The buffer is truncated".to_string()),
+        decode_gray(&bytes[..bytes.len() - 1])
+    );
+    assert_eq!(
+        Err("The buffer is truncated".to_string()),
+        decode_gray(&bytes[..5])
+    );
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert_eq!(
+        Err("The buffer is not a Hilbert grayscale image".to_string()),
+        decode_gray(&wrong_magic)
+    );
+    let mut wrong_order = bytes.clone();
+    wrong_order[12] = 9;
+    assert_eq!(
+        Err("The curve order does not match the dimensions".to_string()),
+        decode_gray(&wrong_order)
+    );
+    let mut extra = bytes.clone();
+    extra.push(2);
+    assert_eq!(
+        Err("There are unexpected bytes after the last pixel".to_string()),
+        decode_gray(&extra)
+    );
+    let mut long_run = encode_gray(&[0; 4], 2, 2);
+    long_run.push(1);
+    assert_eq!(
+        Err("A run of pixels goes past the last pixel".to_string()),
+        decode_gray(&long_run)
+    );
+}
+
+fn sample_images(size: u32) -> Vec<(&'static str, Vec<u8>)> {
+    let mut state: u32 = 12345;
+    let mut random = move || {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        (state >> 16) as u8
+    };
+    let blocks_per_row = size / 8;
+    let block_colors: Vec<u8> = (0..blocks_per_row * blocks_per_row)
+        .map(|_| random())
+        .collect();
+    let coordinates = || (0..size * size).map(move |position| (position % size, position / size));
+    let blocks = coordinates()
+        .map(|(x, y)| block_colors[((y / 8) * blocks_per_row + x / 8) as usize])
+        .collect();
+    let disks = coordinates()
+        .map(|(x, y)| {
+            let (x, y) = (i64::from(x), i64::from(y));
+            let inside =
+                (x - 40).pow(2) + (y - 50).pow(2) < 900 || (x - 90).pow(2) + (y - 80).pow(2) < 1200;
+            if inside {
+                200
+            } else {
+                30
+            }
+        })
+        .collect();
+    let radial = coordinates()
+        .map(|(x, y)| {
+            let (x, y) = (f64::from(x), f64::from(y));
+            (((x - 64.0).powi(2) + (y - 64.0).powi(2)).sqrt() * 2.0) as u8
+        })
+        .collect();
+    vec![("blocks", blocks), ("disks", disks), ("radial", radial)]
+}
+
+#[test]
+fn compression_against_row_major() {
+    // Regions are crossed far less often along the curve than along the rows. A smooth gradient has
+    // small differences in both scans, the rows keep a slight edge
+    let maximum_ratios = [0.2, 1.0, 1.1];
+    for ((name, pixels), maximum_ratio) in sample_images(128).into_iter().zip(maximum_ratios) {
+        let hilbert_size = encode_gray(&pixels, 128, 128).len();
+        let row_major_size = encode_gray_row_major(&pixels, 128, 128).len();
+        let ratio = hilbert_size as f64 / row_major_size as f64;
+        assert!(
+            ratio < maximum_ratio,
+            "{name}: Hilbert {hilbert_size} bytes, row-major {row_major_size} bytes"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+// Every test file uses only some of the fixtures
+#![allow(dead_code)]
+
+use hilbert_curve_rust::CoordinateValue;
+
+/// Deterministic pseudo-random values from a linear congruential generator.
+pub fn random_values(count: usize, seed: u32) -> Vec<u32> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            state >> 8
+        })
+        .collect()
+}
+
+/// Points with both coordinates below `side`.
+pub fn random_points(count: usize, side: u32, seed: u32) -> Vec<CoordinateValue> {
+    random_values(2 * count, seed)
+        .chunks(2)
+        .map(|pair| CoordinateValue {
+            x: pair[0] % side,
+            y: pair[1] % side,
+        })
+        .collect()
+}
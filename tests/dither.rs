@@ -0,0 +1,79 @@
+use hilbert_curve_rust::dither::{dither_gray, dither_rgb, DitherOptions};
+use hilbert_curve_rust::Rgb;
+
+#[test]
+fn gray_keeps_the_mean() {
+    let (width, height) = (37, 23);
+    for level in [32u8, 128, 200] {
+        let pixels = vec![level; width * height];
+        let dithered = dither_gray(
+            &pixels,
+            width as u32,
+            height as u32,
+            &[0, 255],
+            &DitherOptions::default(),
+        );
+        assert!(dithered.iter().all(|value| *value == 0 || *value == 255));
+        let mean =
+            dithered.iter().map(|value| f64::from(*value)).sum::<f64>() / pixels.len() as f64;
+        assert!((mean - f64::from(level)).abs() < 2.0, "{level}: {mean}");
+    }
+}
+
+#[test]
+fn gray_exact_palette_is_unchanged() {
+    let pixels: Vec<u8> = (0..64).map(|value| (value % 4) as u8 * 85).collect();
+    let dithered = dither_gray(&pixels, 8, 8, &[0, 85, 170, 255], &DitherOptions::default());
+    assert_eq!(pixels, dithered);
+}
+
+#[test]
+fn gray_spreads_the_dots() {
+    // A 50% gray on a 2 colors palette alternates along the curve, so no row is a long run
+    let pixels = vec![128u8; 32 * 32];
+    let dithered = dither_gray(&pixels, 32, 32, &[0, 255], &DitherOptions::default());
+    for row in dithered.chunks(32) {
+        let white = row.iter().filter(|value| **value == 255).count();
+        assert!((8..=24).contains(&white));
+    }
+}
+
+#[test]
+fn rgb_uses_the_palette() {
+    let palette = [
+        Rgb::BLACK,
+        Rgb::WHITE,
+        Rgb::new(255, 0, 0),
+        Rgb::new(0, 0, 255),
+    ];
+    let pixels: Vec<Rgb> = (0..30 * 20)
+        .map(|position| Rgb::new((position % 30 * 8) as u8, 40, (position / 30 * 12) as u8))
+        .collect();
+    let options = DitherOptions {
+        queue_length: 8,
+        decay: 0.25,
+    };
+    let dithered = dither_rgb(&pixels, 30, 20, &palette, &options);
+    assert_eq!(pixels.len(), dithered.len());
+    assert!(dithered.iter().all(|color| palette.contains(color)));
+    let mean_red = |colors: &[Rgb]| {
+        colors.iter().map(|color| f64::from(color.r)).sum::<f64>() / colors.len() as f64
+    };
+    assert!((mean_red(&pixels) - mean_red(&dithered)).abs() < 8.0);
+}
+
+#[test]
+#[should_panic(expected = "The palette must have at least one color")]
+fn empty_palette() {
+    dither_gray(&[0; 4], 2, 2, &[], &DitherOptions::default());
+}
+
+#[test]
+#[should_panic(expected = "The decay must be above 0 and at most 1")]
+fn invalid_decay() {
+    let options = DitherOptions {
+        queue_length: 4,
+        decay: 0.0,
+    };
+    dither_gray(&[0; 4], 2, 2, &[0, 255], &options);
+}
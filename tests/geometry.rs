@@ -0,0 +1,110 @@
+use hilbert_curve_rust::geometry::{
+    cell_to_wkt, cells_to_geojson, cells_to_wkt, parse_wkt_polygons, region_to_geojson,
+    region_to_wkt,
+};
+use hilbert_curve_rust::{CoordinateValue, HilbertCell, HilbertRegion, Rectangle};
+
+#[test]
+fn cell_wkt() {
+    // The cell 1 of order 1 is the bottom-left quadrant
+    assert_eq!(
+        "POLYGON ((0 2, 2 2, 2 4, 0 4, 0 2))",
+        cell_to_wkt(&HilbertCell::new(1, 1), 2)
+    );
+    assert_eq!("MULTIPOLYGON EMPTY", cells_to_wkt(&[], 2));
+    assert_eq!(
+        "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 1, 0 0)), ((3 0, 4 0, 4 1, 3 1, 3 0)))",
+        cells_to_wkt(&[HilbertCell::new(2, 0), HilbertCell::new(2, 15)], 2)
+    );
+}
+
+#[test]
+fn cells_geojson() {
+    let json = cells_to_geojson(&[HilbertCell::new(1, 3)], 1);
+    assert_eq!(
+        r#"{"type":"FeatureCollection","features":[{"type":"Feature","geometry":{"type":"Polygon","coordinates":[[[1,0],[2,0],[2,1],[1,1],[1,0]]]},"properties":{"order":1,"index":3}}]}"#,
+        json
+    );
+}
+
+#[test]
+fn region_outline() {
+    let region = HilbertRegion::from_rectangle(4, &Rectangle::new(3, 2, 9, 6));
+    assert_eq!(
+        "MULTIPOLYGON (((3 2, 10 2, 10 7, 3 7, 3 2)))",
+        region_to_wkt(&region)
+    );
+    let json = region_to_geojson(&region);
+    assert!(json
+        .contains(r#""type":"MultiPolygon","coordinates":[[[[3,2],[10,2],[10,7],[3,7],[3,2]]]]"#));
+    assert!(json.contains(r#""properties":{"order":4,"area":35}"#));
+    assert_eq!("MULTIPOLYGON EMPTY", region_to_wkt(&HilbertRegion::new(4)));
+}
+
+#[test]
+fn region_round_trip_through_wkt() {
+    let mut state: u32 = 7;
+    let points: Vec<CoordinateValue> = (0..32 * 32)
+        .filter(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) % 5 < 2
+        })
+        .map(|position| CoordinateValue {
+            x: position % 32,
+            y: position / 32,
+        })
+        .collect();
+    let region = HilbertRegion::from_points(5, points)
+        .union(&HilbertRegion::from_rectangle(
+            5,
+            &Rectangle::new(4, 4, 20, 20),
+        ))
+        .difference(&HilbertRegion::from_rectangle(
+            5,
+            &Rectangle::new(8, 8, 12, 12),
+        ));
+    let polygons = parse_wkt_polygons(&region_to_wkt(&region)).unwrap();
+    let ranges = polygons
+        .iter()
+        .flat_map(|polygon| region.hilbert_curve().ranges_for_polygon(polygon, None));
+    assert_eq!(region, HilbertRegion::from_ranges(5, ranges));
+}
+
+#[test]
+fn parse_polygons() {
+    let polygons =
+        parse_wkt_polygons("polygon ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 4 2, 4 4, 2 4, 2 2))")
+            .unwrap();
+    assert_eq!(1, polygons.len());
+    assert_eq!(4, polygons[0].exterior().len());
+    assert_eq!(1, polygons[0].holes().len());
+    let polygons =
+        parse_wkt_polygons("MULTIPOLYGON Z (((0 0 1, 1.5 0 1, 1.5 -2.5e1 1)), ((5 5, 6 5, 6 6)))")
+            .unwrap();
+    assert_eq!(2, polygons.len());
+    assert_eq!(
+        &[(0.0, 0.0), (1.5, 0.0), (1.5, -25.0)],
+        polygons[0].exterior()
+    );
+    assert!(parse_wkt_polygons("POLYGON EMPTY").unwrap().is_empty());
+    assert!(parse_wkt_polygons("MULTIPOLYGON EMPTY").unwrap().is_empty());
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        Err("The geometry POINT is not a POLYGON or a MULTIPOLYGON".to_string()),
+        parse_wkt_polygons("POINT (1 2)")
+    );
+    assert_eq!(
+        Err("A ring must have at least 3 vertices".to_string()),
+        parse_wkt_polygons("POLYGON ((0 0, 1 1, 0 0))")
+    );
+    assert_eq!(
+        Err("The value 1x is not a number".to_string()),
+        parse_wkt_polygons("POLYGON ((0 0, 1x 1, 0 1))")
+    );
+    assert!(parse_wkt_polygons("POLYGON ((0 0, 1 0, 0 1)").is_err());
+    assert!(parse_wkt_polygons("POLYGON ((0 0, 1 0, 0 1)) trailing").is_err());
+    assert!(parse_wkt_polygons("").is_err());
+}
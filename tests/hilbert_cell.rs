@@ -0,0 +1,95 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertCell, HilbertCurveAlgorithm, Rectangle};
+
+#[test]
+fn cell_parent_and_children() {
+    let cell = HilbertCell::new(2, 7);
+    assert_eq!(Some(HilbertCell::new(1, 1)), cell.parent());
+    assert_eq!(None, HilbertCell::root().parent());
+    let children = cell.children();
+    assert_eq!(HilbertCell::new(3, 28), children[0]);
+    assert_eq!(HilbertCell::new(3, 31), children[3]);
+    assert!(children.iter().all(|child| child.parent() == Some(cell)));
+}
+
+#[test]
+fn cell_contains_descendants_only() {
+    let cell = HilbertCell::new(1, 2);
+    assert!(cell.contains(&cell));
+    assert!(cell.contains(&HilbertCell::new(3, 32)));
+    assert!(cell.contains(&HilbertCell::new(3, 47)));
+    assert!(!cell.contains(&HilbertCell::new(3, 48)));
+    assert!(!cell.contains(&HilbertCell::root()));
+}
+
+#[test]
+fn cell_index_range() {
+    assert_eq!(8..=11, HilbertCell::new(1, 2).index_range(2));
+    assert_eq!(2..=2, HilbertCell::new(1, 2).index_range(1));
+    assert_eq!(0..=u32::MAX, HilbertCell::root().index_range(16));
+}
+
+#[test]
+fn cell_bounds_match_lower_order_curve() {
+    // A cell of order k is the point k of the order k curve, scaled to the finer grid
+    for order in 0..5u16 {
+        let coarse_curve = HilbertCurveAlgorithm::new(order);
+        let side = u32::pow(2, (5 - order).into());
+        for index in 0..u32::pow(4, order.into()) {
+            let point = coarse_curve.index_to_point(index);
+            let expected = Rectangle::new(
+                point.x * side,
+                point.y * side,
+                point.x * side + side - 1,
+                point.y * side + side - 1,
+            );
+            assert_eq!(expected, HilbertCell::new(order, index).bounds(5));
+        }
+    }
+}
+
+#[test]
+fn cell_from_point() {
+    let point = CoordinateValue { x: 3, y: 2 };
+    assert_eq!(
+        HilbertCell::new(2, 11),
+        HilbertCell::from_point(point, 2, 2)
+    );
+    assert_eq!(HilbertCell::new(1, 2), HilbertCell::from_point(point, 2, 1));
+    assert_eq!(HilbertCell::root(), HilbertCell::from_point(point, 2, 0));
+}
+
+#[test]
+fn cell_ordering_parent_before_children() {
+    let mut cells = vec![
+        HilbertCell::new(2, 5),
+        HilbertCell::new(1, 1),
+        HilbertCell::new(2, 4),
+        HilbertCell::root(),
+        HilbertCell::new(1, 0),
+    ];
+    cells.sort();
+    assert_eq!(
+        vec![
+            HilbertCell::root(),
+            HilbertCell::new(1, 0),
+            HilbertCell::new(1, 1),
+            HilbertCell::new(2, 4),
+            HilbertCell::new(2, 5),
+        ],
+        cells
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "The index is above the supported amount of space the current order support. Reduce the index or increase the order."
+)]
+fn cell_index_too_large() {
+    HilbertCell::new(1, 4);
+}
+
+#[test]
+#[should_panic(expected = "A cell of order 16 cannot be split")]
+fn cell_children_of_finest_order() {
+    HilbertCell::new(16, 0).children();
+}
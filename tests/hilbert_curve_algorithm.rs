@@ -1,5 +1,8 @@
 use hilbert_curve_rust::CoordinateValue;
+use hilbert_curve_rust::Direction;
 use hilbert_curve_rust::HilbertCurveAlgorithm;
+use hilbert_curve_rust::Polygon;
+use hilbert_curve_rust::Rectangle;
 
 #[test]
 fn index_to_point_first_order_index_0() {
@@ -257,4 +260,164 @@ fn point_to_index_third_order_point_5_5() {
     let hilbert_curve = HilbertCurveAlgorithm::new(3);
     hilbert_curve.point_to_index(CoordinateValue { x: 8, y: 8 });
 }
-// -------------------------
\ No newline at end of file
+// -------------------------
+#[test]
+fn ranges_for_rectangle_whole_grid_is_one_range() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    let ranges = hilbert_curve.ranges_for_rectangle(&Rectangle::new(0, 0, 7, 7));
+    assert_eq!(vec![0..=63], ranges);
+}
+#[test]
+fn ranges_for_rectangle_second_order_left_half() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let ranges = hilbert_curve.ranges_for_rectangle(&Rectangle::new(0, 0, 1, 3));
+    assert_eq!(vec![0..=7], ranges);
+}
+#[test]
+fn ranges_for_rectangle_second_order_middle_row() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let ranges = hilbert_curve.ranges_for_rectangle(&Rectangle::new(0, 1, 3, 1));
+    assert_eq!(vec![2..=3, 12..=13], ranges);
+}
+#[test]
+fn ranges_for_rectangle_outside_grid() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    assert!(hilbert_curve
+        .ranges_for_rectangle(&Rectangle::new(4, 0, 9, 9))
+        .is_empty());
+}
+#[test]
+fn ranges_for_rectangle_matches_every_cell() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(5);
+    let rectangle = Rectangle::new(3, 7, 20, 12);
+    let ranges = hilbert_curve.ranges_for_rectangle(&rectangle);
+    let mut expected: Vec<u32> = (0..1024)
+        .filter(|index| rectangle.contains(hilbert_curve.index_to_point(*index)))
+        .collect();
+    expected.sort_unstable();
+    let covered: Vec<u32> = ranges.into_iter().flatten().collect();
+    assert_eq!(expected, covered);
+}
+#[test]
+fn ranges_for_rectangle_sixteenth_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(16);
+    let ranges = hilbert_curve.ranges_for_rectangle(&Rectangle::new(0, 0, 65535, 65535));
+    assert_eq!(vec![0..=u32::MAX], ranges);
+}
+#[test]
+fn iter_third_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    let points: Vec<CoordinateValue> = hilbert_curve.iter().collect();
+    assert_eq!(64, hilbert_curve.iter().len());
+    for (index, point) in points.into_iter().enumerate() {
+        assert_eq!(hilbert_curve.index_to_point(index as u32), point);
+    }
+}
+#[test]
+fn neighbor_matches_point_to_index() {
+    for order in 1..=4 {
+        let hilbert_curve = HilbertCurveAlgorithm::new(order);
+        for (index, point) in hilbert_curve.iter().enumerate() {
+            for direction in Direction::ALL {
+                let expected = direction
+                    .step(point, hilbert_curve.number_of_rows())
+                    .map(|next| (next, hilbert_curve.point_to_index(next)));
+                assert_eq!(expected, hilbert_curve.neighbor(point, index as u32, direction));
+            }
+        }
+    }
+}
+#[test]
+fn neighbor_sixteenth_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(16);
+    let point = CoordinateValue { x: 32767, y: 40000 };
+    let index = hilbert_curve.point_to_index(point);
+    let (next, next_index) = hilbert_curve.neighbor(point, index, Direction::Right).unwrap();
+    assert_eq!(CoordinateValue { x: 32768, y: 40000 }, next);
+    assert_eq!(hilbert_curve.point_to_index(next), next_index);
+    assert_eq!(None, hilbert_curve.neighbor(CoordinateValue { x: 65535, y: 0 }, 0, Direction::Right));
+}
+#[test]
+fn ranges_for_polygon_with_hole() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(4);
+    let polygon = Polygon::with_holes(
+        vec![(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0)],
+        vec![vec![(2.0, 2.0), (6.0, 2.0), (6.0, 6.0), (2.0, 6.0)]],
+    );
+    let ranges = hilbert_curve.ranges_for_polygon(&polygon, None);
+    let cells: u32 = ranges.iter().map(|range| range.end() - range.start() + 1).sum();
+    assert_eq!(48, cells);
+    let hole_index = hilbert_curve.point_to_index(CoordinateValue { x: 3, y: 4 });
+    assert!(!ranges.iter().any(|range| range.contains(&hole_index)));
+}
+#[test]
+fn ranges_for_polygon_touching_itself() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    // Two squares sharing the corner (4, 4), drawn as one ring
+    let polygon = Polygon::new(vec![
+        (0.0, 0.0),
+        (4.0, 0.0),
+        (4.0, 4.0),
+        (8.0, 4.0),
+        (8.0, 8.0),
+        (4.0, 8.0),
+        (4.0, 4.0),
+        (0.0, 4.0),
+    ]);
+    let ranges = hilbert_curve.ranges_for_polygon(&polygon, None);
+    let expected: Vec<u32> = (0..64)
+        .filter(|index| {
+            let point = hilbert_curve.index_to_point(*index);
+            (point.x < 4) == (point.y < 4)
+        })
+        .collect();
+    let covered: Vec<u32> = ranges.into_iter().flatten().collect();
+    assert_eq!(expected, covered);
+}
+#[test]
+fn ranges_for_polygon_holds_every_cell_center() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(5);
+    let polygon = Polygon::new(vec![(1.5, 2.0), (30.0, 7.25), (12.0, 29.5)]);
+    let ranges = hilbert_curve.ranges_for_polygon(&polygon, None);
+    for index in 0..1024 {
+        let point = hilbert_curve.index_to_point(index);
+        if polygon.contains(point.x as f64 + 0.5, point.y as f64 + 0.5) {
+            assert!(ranges.iter().any(|range| range.contains(&index)));
+        }
+    }
+    let coarse = hilbert_curve.ranges_for_polygon(&polygon, Some(4));
+    assert!(coarse.len() <= 4);
+    for range in ranges {
+        assert!(coarse.iter().any(|coarse_range| coarse_range.contains(range.start()) && coarse_range.contains(range.end())));
+    }
+}
+#[test]
+fn ranges_for_circle_matches_distances() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(5);
+    let (center, radius) = ((13.3, 17.8), 9.5);
+    let ranges = hilbert_curve.ranges_for_circle(center, radius, None);
+    for index in 0..1024 {
+        let point = hilbert_curve.index_to_point(index);
+        let dx = center.0 - (center.0).clamp(point.x as f64, point.x as f64 + 1.0);
+        let dy = center.1 - (center.1).clamp(point.y as f64, point.y as f64 + 1.0);
+        let overlaps = dx * dx + dy * dy < radius * radius;
+        assert_eq!(overlaps, ranges.iter().any(|range| range.contains(&index)));
+    }
+    assert_eq!(2, hilbert_curve.ranges_for_circle(center, radius, Some(2)).len());
+}
+
+#[test]
+fn range_to_rectangles_matches_every_cell() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(4);
+    for range in [0..=255, 3..=3, 5..=200, 17..=94, 100..=101] {
+        let rectangles = hilbert_curve.range_to_rectangles(&range);
+        let area: u64 = rectangles.iter().map(|rectangle| rectangle.area()).sum();
+        assert_eq!(u64::from(range.end() - range.start()) + 1, area);
+        for index in 0..256 {
+            let point = hilbert_curve.index_to_point(index);
+            let covered = rectangles.iter().filter(|rectangle| rectangle.contains(point)).count();
+            assert_eq!(usize::from(range.contains(&index)), covered);
+        }
+    }
+    assert_eq!(1, hilbert_curve.range_to_rectangles(&(0..=127)).len());
+}
@@ -0,0 +1,69 @@
+use hilbert_curve_rust::{CoordinateValue, Direction, HilbertCurveAlgorithm, HilbertGrid};
+
+fn point(x: u32, y: u32) -> CoordinateValue {
+    CoordinateValue { x, y }
+}
+
+#[test]
+fn stored_in_curve_order() {
+    let mut grid = HilbertGrid::new(1, 0);
+    grid[point(1, 0)] = 7;
+    assert_eq!(&[0, 0, 0, 7], grid.as_slice());
+    assert_eq!(7, grid[point(1, 0)]);
+    assert_eq!(Some(&7), grid.get(point(1, 0)));
+    assert_eq!(None, grid.get(point(2, 0)));
+    *grid.get_mut(point(0, 1)).unwrap() = 3;
+    assert_eq!(&[0, 3, 0, 7], grid.as_slice());
+}
+
+#[test]
+fn row_major_round_trip() {
+    let values: Vec<u32> = (0..64).collect();
+    let grid = HilbertGrid::from_row_major(3, values.clone());
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    for (index, value) in grid.as_slice().iter().enumerate() {
+        let point = hilbert_curve.index_to_point(index as u32);
+        assert_eq!(point.y * 8 + point.x, *value);
+    }
+    assert_eq!(values, grid.to_row_major());
+}
+
+#[test]
+fn iterations() {
+    let grid = HilbertGrid::from_row_major(2, (0..16).collect::<Vec<u32>>());
+    let curve: Vec<CoordinateValue> = grid.iter().map(|(point, _)| point).collect();
+    assert_eq!(
+        HilbertCurveAlgorithm::new(2).iter().collect::<Vec<_>>(),
+        curve
+    );
+    let row_major: Vec<(CoordinateValue, u32)> = grid
+        .iter_row_major()
+        .map(|(point, value)| (point, *value))
+        .collect();
+    assert_eq!(16, row_major.len());
+    for (position, (point, value)) in row_major.into_iter().enumerate() {
+        assert_eq!(position as u32, value);
+        assert_eq!(
+            (position as u32 % 4, position as u32 / 4),
+            (point.x, point.y)
+        );
+    }
+}
+
+#[test]
+fn neighbor_value() {
+    let grid = HilbertGrid::from_row_major(2, (0..16).collect::<Vec<u32>>());
+    let start = point(1, 1);
+    let index = grid.hilbert_curve().point_to_index(start);
+    let (next, next_index, value) = grid.neighbor(start, index, Direction::Down).unwrap();
+    assert_eq!(point(1, 2), next);
+    assert_eq!(grid.hilbert_curve().point_to_index(next), next_index);
+    assert_eq!(9, *value);
+    assert!(grid.neighbor(point(0, 0), 0, Direction::Up).is_none());
+}
+
+#[test]
+#[should_panic(expected = "The number of values must be the number of cells of the grid")]
+fn from_row_major_wrong_length() {
+    HilbertGrid::from_row_major(2, vec![0; 15]);
+}
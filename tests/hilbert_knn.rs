@@ -0,0 +1,112 @@
+mod common;
+
+use common::random_points;
+use hilbert_curve_rust::{CoordinateValue, HilbertCurveAlgorithm, HilbertKnn};
+
+fn brute_force(points: &[CoordinateValue], query: CoordinateValue, k: usize) -> Vec<u64> {
+    let mut distances: Vec<u64> = points
+        .iter()
+        .map(|point| {
+            let dx = u64::from(point.x.abs_diff(query.x));
+            let dy = u64::from(point.y.abs_diff(query.y));
+            dx * dx + dy * dy
+        })
+        .collect();
+    distances.sort_unstable();
+    distances.truncate(k);
+    distances
+}
+
+#[test]
+fn matches_brute_force() {
+    let points = random_points(2000, 1024, 3);
+    let knn = HilbertKnn::from_points(10, points.clone());
+    let mut total_candidates = 0;
+    let queries = random_points(200, 1024, 11);
+    for query in &queries {
+        for k in [1, 5, 20] {
+            let (neighbors, candidates) = knn.nearest_counted(*query, k);
+            let distances: Vec<u64> = neighbors.iter().map(|(_, distance)| *distance).collect();
+            assert_eq!(brute_force(&points, *query, k), distances);
+            for (point, distance) in neighbors {
+                let dx = u64::from(point.x.abs_diff(query.x));
+                let dy = u64::from(point.y.abs_diff(query.y));
+                assert_eq!(dx * dx + dy * dy, distance);
+            }
+            if k == 5 {
+                total_candidates += candidates;
+            }
+        }
+    }
+    // The brute force computes every distance, the search only a small fraction
+    let average = total_candidates as f64 / queries.len() as f64;
+    assert!(average < points.len() as f64 / 10.0);
+}
+
+#[test]
+fn clustered_points_and_duplicates() {
+    let mut points = random_points(300, 16, 5);
+    points.extend(random_points(300, 1 << 14, 9));
+    points.extend(vec![CoordinateValue { x: 5000, y: 5000 }; 10]);
+    let knn = HilbertKnn::from_points(14, points.clone());
+    for query in [
+        CoordinateValue { x: 0, y: 0 },
+        CoordinateValue { x: 5000, y: 5001 },
+        CoordinateValue { x: 16383, y: 16383 },
+        CoordinateValue { x: 8192, y: 8191 },
+    ] {
+        let distances: Vec<u64> = knn
+            .nearest(query, 15)
+            .into_iter()
+            .map(|(_, distance)| distance)
+            .collect();
+        assert_eq!(brute_force(&points, query, 15), distances);
+    }
+}
+
+#[test]
+fn fewer_points_than_k() {
+    let knn = HilbertKnn::from_points(
+        4,
+        vec![
+            CoordinateValue { x: 1, y: 1 },
+            CoordinateValue { x: 9, y: 9 },
+        ],
+    );
+    let neighbors = knn.nearest(CoordinateValue { x: 8, y: 8 }, 5);
+    assert_eq!(
+        vec![
+            (CoordinateValue { x: 9, y: 9 }, 2),
+            (CoordinateValue { x: 1, y: 1 }, 98)
+        ],
+        neighbors
+    );
+    assert!(knn.nearest(CoordinateValue { x: 8, y: 8 }, 0).is_empty());
+    assert!(HilbertKnn::from_points(4, vec![])
+        .nearest(CoordinateValue { x: 0, y: 0 }, 3)
+        .is_empty());
+}
+
+#[test]
+fn from_sorted_pairs() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    let points: Vec<(u32, CoordinateValue)> = (0..64)
+        .step_by(7)
+        .map(|index| (index, hilbert_curve.index_to_point(index)))
+        .collect();
+    let knn = HilbertKnn::new(3, points);
+    assert_eq!(10, knn.len());
+    assert_eq!(0, knn.nearest(hilbert_curve.index_to_point(14), 1)[0].1);
+}
+
+#[test]
+#[should_panic(expected = "The points must be sorted by Hilbert index")]
+fn unsorted_pairs() {
+    HilbertKnn::new(
+        3,
+        vec![
+            (5, CoordinateValue { x: 0, y: 0 }),
+            (2, CoordinateValue { x: 0, y: 0 }),
+        ],
+    );
+}
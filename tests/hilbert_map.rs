@@ -0,0 +1,75 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertMap, Rectangle};
+use std::collections::btree_map::Entry;
+
+fn point(x: u32, y: u32) -> CoordinateValue {
+    CoordinateValue { x, y }
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = HilbertMap::new(4);
+    assert!(map.is_empty());
+    assert_eq!(None, map.insert(point(3, 4), "a"));
+    assert_eq!(Some("a"), map.insert(point(3, 4), "b"));
+    assert_eq!(Some(&"b"), map.get(point(3, 4)));
+    assert!(map.contains_point(point(3, 4)));
+    assert!(!map.contains_point(point(4, 3)));
+    *map.get_mut(point(3, 4)).unwrap() = "c";
+    assert_eq!(1, map.len());
+    assert_eq!(Some("c"), map.remove(point(3, 4)));
+    assert_eq!(None, map.remove(point(3, 4)));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn iter_in_curve_order() {
+    let mut map = HilbertMap::new(2);
+    map.extend(vec![(point(3, 0), 15), (point(0, 0), 0), (point(1, 2), 7)]);
+    let entries: Vec<(CoordinateValue, i32)> =
+        map.iter().map(|(point, value)| (point, *value)).collect();
+    assert_eq!(
+        vec![(point(0, 0), 0), (point(1, 2), 7), (point(3, 0), 15)],
+        entries
+    );
+    for (_, value) in map.iter_mut() {
+        *value += 1;
+    }
+    assert_eq!(vec![1, 8, 16], map.values().copied().collect::<Vec<i32>>());
+}
+
+#[test]
+fn entry_counts_points() {
+    let mut map = HilbertMap::new(3);
+    for p in [point(1, 1), point(2, 2), point(1, 1)] {
+        *map.entry(p).or_insert(0) += 1;
+    }
+    assert_eq!(Some(&2), map.get(point(1, 1)));
+    match map.entry(point(2, 2)) {
+        Entry::Occupied(entry) => assert_eq!(1, *entry.get()),
+        Entry::Vacant(_) => panic!("The entry must exist"),
+    }
+}
+
+#[test]
+fn range_rect_matches_full_scan() {
+    let mut map = HilbertMap::new(6);
+    for i in 0..2000u32 {
+        map.insert(point((i * 37) % 64, (i * 11) % 64), i);
+    }
+    let rectangle = Rectangle::new(10, 20, 30, 25);
+    let found: Vec<CoordinateValue> = map.range_rect(&rectangle).map(|(point, _)| point).collect();
+    let expected: Vec<CoordinateValue> = map
+        .iter()
+        .map(|(point, _)| point)
+        .filter(|point| rectangle.contains(*point))
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn range_rect_outside_grid() {
+    let mut map = HilbertMap::new(2);
+    map.insert(point(0, 0), ());
+    assert_eq!(0, map.range_rect(&Rectangle::new(5, 5, 6, 6)).count());
+}
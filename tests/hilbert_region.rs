@@ -0,0 +1,172 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertCurveAlgorithm, HilbertRegion, Rectangle};
+
+fn point(x: u32, y: u32) -> CoordinateValue {
+    CoordinateValue { x, y }
+}
+
+#[test]
+fn from_ranges_sorts_and_merges() {
+    let region = HilbertRegion::from_ranges(2, vec![8..=9, 0..=2, 3..=4, 9..=12, 14..=14]);
+    assert_eq!(&[0..=4, 8..=12, 14..=14], region.ranges());
+    assert_eq!(11, region.area());
+}
+
+#[test]
+fn from_rectangle() {
+    let rectangle = Rectangle::new(3, 2, 12, 9);
+    let region = HilbertRegion::from_rectangle(4, &rectangle);
+    assert_eq!(rectangle.area(), region.area());
+    assert_eq!(Some(rectangle), region.bounding_box());
+    assert!(region.points().all(|point| rectangle.contains(point)));
+}
+
+#[test]
+fn from_points() {
+    let region =
+        HilbertRegion::from_points(1, vec![point(1, 0), point(0, 0), point(0, 1), point(0, 0)]);
+    assert_eq!(&[0..=1, 3..=3], region.ranges());
+    assert_eq!(
+        vec![point(0, 0), point(0, 1), point(1, 0)],
+        region.points().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn mask_round_trip() {
+    let (width, height) = (13u32, 9u32);
+    let mask: Vec<bool> = (0..width * height)
+        .map(|position| {
+            let (x, y) = ((position % width) as i32, (position / width) as i32);
+            (x - 6).pow(2) + (y - 4).pow(2) <= 12
+        })
+        .collect();
+    let region = HilbertRegion::from_mask(4, &mask, width, height);
+    assert_eq!(
+        mask.iter().filter(|set| **set).count() as u64,
+        region.area()
+    );
+    assert!(region.ranges().len() < region.area() as usize);
+    assert_eq!(mask, region.to_mask(width, height));
+    assert_eq!(Some(Rectangle::new(3, 1, 9, 7)), region.bounding_box());
+}
+
+#[test]
+fn to_mask_crops() {
+    let region = HilbertRegion::from_rectangle(3, &Rectangle::new(2, 2, 7, 7));
+    let mask = region.to_mask(4, 3);
+    assert_eq!(
+        vec![
+            false, false, false, false, //
+            false, false, false, false, //
+            false, false, true, true,
+        ],
+        mask
+    );
+}
+
+#[test]
+fn empty_region() {
+    let region = HilbertRegion::new(5);
+    assert!(region.is_empty());
+    assert_eq!(0, region.area());
+    assert_eq!(None, region.bounding_box());
+    assert_eq!(vec![false; 6], region.to_mask(3, 2));
+}
+
+#[test]
+fn whole_sixteenth_order() {
+    let region = HilbertRegion::from_ranges(16, vec![0..=u32::MAX]);
+    assert_eq!(1 << 32, region.area());
+    assert_eq!(
+        Some(Rectangle::new(0, 0, 65535, 65535)),
+        region.bounding_box()
+    );
+    let corner = HilbertCurveAlgorithm::new(16).point_to_index(point(65535, 0));
+    assert_eq!(
+        Some(Rectangle::new(65535, 0, 65535, 0)),
+        HilbertRegion::from_ranges(16, vec![corner..=corner]).bounding_box()
+    );
+}
+
+#[test]
+#[should_panic(expected = "The mask must fit in the grid of the order")]
+fn mask_too_large() {
+    HilbertRegion::from_mask(2, &[false; 25], 5, 5);
+}
+
+fn random_region(order: u16, seed: u32) -> HilbertRegion {
+    let mut state = seed;
+    let side = 1u32 << order;
+    let points: Vec<CoordinateValue> = (0..side * side)
+        .filter(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16).is_multiple_of(3)
+        })
+        .map(|position| point(position % side, position / side))
+        .collect();
+    HilbertRegion::from_points(order, points)
+}
+
+#[test]
+fn set_operations_match_masks() {
+    let (left, right) = (random_region(4, 1), random_region(4, 2));
+    let (left_mask, right_mask) = (left.to_mask(16, 16), right.to_mask(16, 16));
+    let apply = |keep: fn(bool, bool) -> bool| -> Vec<bool> {
+        left_mask
+            .iter()
+            .zip(&right_mask)
+            .map(|(a, b)| keep(*a, *b))
+            .collect()
+    };
+    assert_eq!(apply(|a, b| a || b), left.union(&right).to_mask(16, 16));
+    assert_eq!(
+        apply(|a, b| a && b),
+        left.intersection(&right).to_mask(16, 16)
+    );
+    assert_eq!(
+        apply(|a, b| a && !b),
+        left.difference(&right).to_mask(16, 16)
+    );
+    assert_eq!(apply(|a, b| a != b), left.xor(&right).to_mask(16, 16));
+    assert_eq!(apply(|a, _| !a), left.complement().to_mask(16, 16));
+    let union = left.union(&right);
+    assert!(union
+        .ranges()
+        .windows(2)
+        .all(|pair| u64::from(*pair[0].end()) + 1 < u64::from(*pair[1].start())));
+}
+
+#[test]
+fn set_operations_on_ranges() {
+    let left = HilbertRegion::from_ranges(2, vec![0..=5, 10..=12]);
+    let right = HilbertRegion::from_ranges(2, vec![4..=10, 15..=15]);
+    assert_eq!(&[0..=12, 15..=15], left.union(&right).ranges());
+    assert_eq!(&[4..=5, 10..=10], left.intersection(&right).ranges());
+    assert_eq!(&[0..=3, 11..=12], left.difference(&right).ranges());
+    assert_eq!(&[0..=3, 6..=9, 11..=12, 15..=15], left.xor(&right).ranges());
+    assert_eq!(&[6..=9, 13..=15], left.complement().ranges());
+    assert!(HilbertRegion::new(2).complement().ranges() == [0..=15]);
+    assert!(HilbertRegion::from_ranges(16, vec![0..=u32::MAX])
+        .complement()
+        .is_empty());
+}
+
+#[test]
+fn predicates() {
+    let region = HilbertRegion::from_rectangle(3, &Rectangle::new(2, 2, 4, 5));
+    assert!(region.contains_point(point(2, 2)));
+    assert!(region.contains_point(point(4, 5)));
+    assert!(!region.contains_point(point(5, 5)));
+    assert!(!region.contains_point(point(0, 0)));
+    let touching = HilbertRegion::from_rectangle(3, &Rectangle::new(4, 5, 7, 7));
+    let apart = HilbertRegion::from_rectangle(3, &Rectangle::new(5, 0, 7, 4));
+    assert!(region.intersects(&touching));
+    assert!(!region.intersects(&apart));
+    assert!(!region.intersects(&HilbertRegion::new(3)));
+}
+
+#[test]
+#[should_panic(expected = "The regions must have the same order")]
+fn different_orders() {
+    HilbertRegion::new(2).union(&HilbertRegion::new(3));
+}
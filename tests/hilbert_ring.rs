@@ -0,0 +1,184 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertRing, Rectangle};
+use std::collections::HashMap;
+
+const NODES: [&str; 6] = ["alpha", "beta", "gamma", "delta", "epsilon", "zeta"];
+
+fn ring(order: u16, virtual_nodes: u32, nodes: &[&'static str]) -> HilbertRing<&'static str> {
+    let mut ring = HilbertRing::new(order, virtual_nodes);
+    for node in nodes {
+        ring.add_node(*node);
+    }
+    ring
+}
+
+fn owned_cells(ring: &HilbertRing<&'static str>) -> HashMap<&'static str, u64> {
+    let mut owned = HashMap::new();
+    for (range, node) in ring.arcs() {
+        *owned.entry(*node).or_insert(0) += u64::from(range.end() - range.start()) + 1;
+    }
+    owned
+}
+
+#[test]
+fn arcs_cover_the_curve() {
+    let ring = ring(5, 4, &NODES);
+    let arcs = ring.arcs();
+    assert_eq!(0, *arcs[0].0.start());
+    assert_eq!(1023, *arcs[arcs.len() - 1].0.end());
+    for pair in arcs.windows(2) {
+        assert_eq!(*pair[0].0.end() + 1, *pair[1].0.start());
+        assert_ne!(pair[0].1, pair[1].1);
+    }
+    for (range, node) in &arcs {
+        for index in range.clone() {
+            assert_eq!(Some(*node), ring.owner(index));
+        }
+    }
+    let point = CoordinateValue { x: 7, y: 30 };
+    let index = ring.hilbert_curve().point_to_index(point);
+    assert_eq!(ring.owner(index), ring.owner_of_point(point));
+}
+
+#[test]
+fn empty_ring() {
+    let ring: HilbertRing<&str> = HilbertRing::new(4, 8);
+    assert!(ring.is_empty());
+    assert_eq!(None, ring.owner(5));
+    assert!(ring.arcs().is_empty());
+    assert!(ring.shards_for_rect(&Rectangle::new(0, 0, 3, 3)).is_empty());
+}
+
+#[test]
+fn single_token_per_node_gives_one_local_arc() {
+    let ring = ring(6, 1, &NODES[..3]);
+    // The node owning the wrap-around arc owns both ends of the curve
+    assert!(ring.arcs().len() <= 4);
+    assert_eq!(ring.owner(0), ring.owner(4095));
+}
+
+#[test]
+fn balanced_with_many_virtual_nodes() {
+    let ring = ring(10, 128, &NODES);
+    let owned = owned_cells(&ring);
+    let ideal = (1u64 << 20) as f64 / NODES.len() as f64;
+    assert_eq!(NODES.len(), owned.len());
+    for cells in owned.values() {
+        let ratio = *cells as f64 / ideal;
+        assert!(ratio > 0.7 && ratio < 1.3, "{ratio}");
+    }
+}
+
+#[test]
+fn positions_do_not_depend_on_the_run() {
+    // Pinned values: a randomly seeded hasher would move the tokens on every run
+    let ring = ring(8, 16, &NODES);
+    let expected = [
+        (0..=56, "beta"),
+        (57..=1215, "epsilon"),
+        (1216..=2745, "alpha"),
+        (2746..=3366, "delta"),
+        (3367..=4360, "epsilon"),
+    ];
+    let arcs: Vec<_> = ring
+        .arcs()
+        .into_iter()
+        .take(5)
+        .map(|(range, node)| (range, *node))
+        .collect();
+    assert_eq!(expected.to_vec(), arcs);
+}
+
+#[test]
+fn add_node_moves_only_keys_to_the_new_node() {
+    let mut ring = ring(5, 8, &NODES[..5]);
+    let before: Vec<_> = (0..1024).map(|index| *ring.owner(index).unwrap()).collect();
+    let handoffs = ring.add_node("zeta");
+    assert!(!handoffs.is_empty());
+    let mut moved = 0;
+    for handoff in &handoffs {
+        assert_eq!("zeta", handoff.to);
+        for index in handoff.range.clone() {
+            assert_eq!(before[index as usize], handoff.from);
+        }
+        moved += u64::from(handoff.range.end() - handoff.range.start()) + 1;
+    }
+    assert_eq!(owned_cells(&ring)["zeta"], moved);
+    for index in 0..1024u32 {
+        let handed = handoffs
+            .iter()
+            .any(|handoff| handoff.range.contains(&index));
+        assert_eq!(
+            handed,
+            before[index as usize] != *ring.owner(index).unwrap()
+        );
+    }
+    assert!(ring.add_node("zeta").is_empty());
+    assert_eq!(6, ring.len());
+}
+
+#[test]
+fn remove_node_hands_its_keys_to_the_others() {
+    let mut ring = ring(5, 8, &NODES);
+    let gamma_cells = owned_cells(&ring)["gamma"];
+    let handoffs = ring.remove_node(&"gamma").unwrap();
+    assert!(handoffs.iter().all(|handoff| handoff.from == "gamma"));
+    let moved: u64 = handoffs
+        .iter()
+        .map(|handoff| u64::from(handoff.range.end() - handoff.range.start()) + 1)
+        .sum();
+    assert_eq!(gamma_cells, moved);
+    assert!(!ring.contains_node(&"gamma"));
+    assert_eq!(None, ring.remove_node(&"gamma"));
+    assert_eq!(
+        ring.arcs(),
+        self::ring(5, 8, &["alpha", "beta", "delta", "epsilon", "zeta"]).arcs()
+    );
+}
+
+#[test]
+fn stacked_tokens() {
+    let ring = ring(0, 3, &NODES);
+    assert_eq!(1, ring.arcs().len());
+    let owner = ring.owner(0);
+    assert_eq!(
+        vec![owner.unwrap()],
+        ring.shards_for_rect(&Rectangle::new(0, 0, 0, 0))
+    );
+}
+
+#[test]
+fn remove_last_node() {
+    let mut ring = ring(3, 2, &["alpha"]);
+    assert_eq!(Some(Vec::new()), ring.remove_node(&"alpha"));
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn shards_for_rect_matches_every_cell() {
+    let ring = ring(5, 6, &NODES);
+    for rectangle in [
+        Rectangle::new(0, 0, 31, 31),
+        Rectangle::new(3, 4, 9, 20),
+        Rectangle::new(17, 17, 17, 17),
+        Rectangle::new(10, 25, 31, 28),
+    ] {
+        let shards = ring.shards_for_rect(&rectangle);
+        let mut expected = Vec::new();
+        for y in rectangle.y_min..=rectangle.y_max {
+            for x in rectangle.x_min..=rectangle.x_max {
+                let node = ring.owner_of_point(CoordinateValue { x, y }).unwrap();
+                if !expected.contains(&node) {
+                    expected.push(node);
+                }
+            }
+        }
+        assert_eq!(expected.len(), shards.len());
+        assert!(expected.iter().all(|node| shards.contains(node)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "The number of virtual nodes must be at least one")]
+fn zero_virtual_nodes() {
+    HilbertRing::<&str>::new(4, 0);
+}
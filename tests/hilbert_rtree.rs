@@ -0,0 +1,144 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertRTree, Rectangle};
+
+fn boxes(count: u32) -> Vec<(Rectangle, u32)> {
+    (0..count)
+        .map(|id| {
+            let x = id.wrapping_mul(2654435761) % 1000;
+            let y = id.wrapping_mul(40503) % 1000;
+            (Rectangle::new(x, y, x + id % 7, y + id % 5), id)
+        })
+        .collect()
+}
+
+fn squared_distance(rectangle: &Rectangle, point: CoordinateValue) -> u64 {
+    let dx = u64::from(
+        rectangle
+            .x_min
+            .saturating_sub(point.x)
+            .max(point.x.saturating_sub(rectangle.x_max)),
+    );
+    let dy = u64::from(
+        rectangle
+            .y_min
+            .saturating_sub(point.y)
+            .max(point.y.saturating_sub(rectangle.y_max)),
+    );
+    dx * dx + dy * dy
+}
+
+#[test]
+fn search_matches_brute_force() {
+    let items = boxes(1000);
+    let tree = HilbertRTree::new(items.clone(), 8);
+    assert_eq!(1000, tree.len());
+    for query in [
+        Rectangle::new(0, 0, 100, 100),
+        Rectangle::new(500, 200, 520, 900),
+        Rectangle::new(999, 999, 1500, 1500),
+        Rectangle::new(2000, 2000, 2001, 2001),
+    ] {
+        let mut found: Vec<u32> = tree.search(&query).into_iter().copied().collect();
+        found.sort_unstable();
+        let expected: Vec<u32> = items
+            .iter()
+            .filter(|(rectangle, _)| rectangle.intersects(&query))
+            .map(|(_, id)| *id)
+            .collect();
+        assert_eq!(expected, found);
+    }
+}
+
+#[test]
+fn nearest_matches_brute_force() {
+    let items = boxes(500);
+    let tree = HilbertRTree::new(items.clone(), 4);
+    let point = CoordinateValue { x: 420, y: 610 };
+    let found = tree.nearest(point, 10);
+    assert_eq!(10, found.len());
+    let mut distances: Vec<u64> = items
+        .iter()
+        .map(|(rectangle, _)| squared_distance(rectangle, point))
+        .collect();
+    distances.sort_unstable();
+    let found_distances: Vec<u64> = found
+        .iter()
+        .map(|id| squared_distance(&items[**id as usize].0, point))
+        .collect();
+    assert_eq!(distances[..10].to_vec(), found_distances);
+}
+
+#[test]
+fn nearest_more_than_available() {
+    let tree = HilbertRTree::new(boxes(3), 2);
+    assert_eq!(3, tree.nearest(CoordinateValue { x: 0, y: 0 }, 10).len());
+    assert!(tree.nearest(CoordinateValue { x: 0, y: 0 }, 0).is_empty());
+}
+
+#[test]
+fn bounds_and_iteration_order() {
+    let tree = HilbertRTree::new(
+        vec![
+            (Rectangle::new(10, 10, 10, 10), "far"),
+            (Rectangle::new(0, 0, 1, 1), "origin"),
+        ],
+        2,
+    );
+    assert_eq!(Some(Rectangle::new(0, 0, 10, 10)), tree.bounds());
+    let names: Vec<&str> = tree.iter().map(|(_, name)| *name).collect();
+    assert_eq!(vec!["origin", "far"], names);
+}
+
+#[test]
+fn empty_tree() {
+    let tree: HilbertRTree<u32> = HilbertRTree::new(Vec::new(), 16);
+    assert!(tree.is_empty());
+    assert_eq!(None, tree.bounds());
+    assert!(tree.search(&Rectangle::new(0, 0, 10, 10)).is_empty());
+    assert!(tree.nearest(CoordinateValue { x: 0, y: 0 }, 1).is_empty());
+}
+
+#[test]
+fn bytes_round_trip() {
+    let tree = HilbertRTree::new(boxes(300), 6);
+    let bytes = tree.to_bytes(|id, buffer| buffer.extend_from_slice(&id.to_le_bytes()));
+    let loaded = HilbertRTree::from_bytes(&bytes, |payload| {
+        Some(u32::from_le_bytes(payload.try_into().ok()?))
+    })
+    .unwrap();
+    assert_eq!(tree.len(), loaded.len());
+    assert_eq!(6, loaded.node_size());
+    assert_eq!(tree.bounds(), loaded.bounds());
+    let query = Rectangle::new(100, 100, 400, 400);
+    assert_eq!(tree.search(&query), loaded.search(&query));
+}
+
+#[test]
+fn from_bytes_rejects_invalid_buffers() {
+    let tree = HilbertRTree::new(boxes(20), 4);
+    let bytes = tree.to_bytes(|id, buffer| buffer.extend_from_slice(&id.to_le_bytes()));
+    let decode = |payload: &[u8]| Some(u32::from_le_bytes(payload.try_into().ok()?));
+    assert_eq!(
+        Err("The buffer is truncated".to_string()),
+        HilbertRTree::from_bytes(&bytes[..bytes.len() - 1], decode).map(|tree| tree.len())
+    );
+    assert_eq!(
+        Err("The buffer is not a Hilbert R-tree".to_string()),
+        HilbertRTree::from_bytes(b"nope", decode).map(|tree| tree.len())
+    );
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert_eq!(
+        Err("There are unexpected bytes after the last item".to_string()),
+        HilbertRTree::from_bytes(&extra, decode).map(|tree| tree.len())
+    );
+    assert_eq!(
+        Err("An item cannot be decoded".to_string()),
+        HilbertRTree::<u32>::from_bytes(&bytes, |_| None).map(|tree| tree.len())
+    );
+}
+
+#[test]
+#[should_panic(expected = "The node size must be at least 2")]
+fn node_size_too_small() {
+    HilbertRTree::new(boxes(3), 1);
+}
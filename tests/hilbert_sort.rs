@@ -0,0 +1,91 @@
+use hilbert_curve_rust::{
+    hilbert_sort, hilbert_sort_cached, hilbert_sort_f64, CoordinateValue, HilbertCurveAlgorithm,
+};
+
+struct Record {
+    name: &'static str,
+    point: CoordinateValue,
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record {
+            name: "d",
+            point: CoordinateValue { x: 3, y: 0 },
+        },
+        Record {
+            name: "b",
+            point: CoordinateValue { x: 1, y: 2 },
+        },
+        Record {
+            name: "a",
+            point: CoordinateValue { x: 0, y: 0 },
+        },
+        Record {
+            name: "c",
+            point: CoordinateValue { x: 3, y: 2 },
+        },
+    ]
+}
+
+#[test]
+fn hilbert_sort_second_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let mut items = records();
+    hilbert_sort(&mut items, &hilbert_curve, |record| record.point);
+    let names: Vec<&str> = items.iter().map(|record| record.name).collect();
+    assert_eq!(vec!["a", "b", "c", "d"], names);
+}
+
+#[test]
+fn hilbert_sort_cached_same_as_uncached() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(5);
+    let mut points: Vec<CoordinateValue> = (0..32u32)
+        .flat_map(|x| (0..32u32).map(move |y| CoordinateValue { x: (x * 7) % 32, y }))
+        .collect();
+    let mut cached = points.clone();
+    hilbert_sort(&mut points, &hilbert_curve, |point| *point);
+    hilbert_sort_cached(&mut cached, &hilbert_curve, |point| *point);
+    assert_eq!(points, cached);
+    for (index, point) in cached.iter().enumerate() {
+        assert_eq!(index as u32, hilbert_curve.point_to_index(*point));
+    }
+}
+
+#[test]
+fn hilbert_sort_cached_is_stable() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let mut items = vec![
+        (CoordinateValue { x: 1, y: 0 }, 0),
+        (CoordinateValue { x: 0, y: 0 }, 1),
+        (CoordinateValue { x: 1, y: 0 }, 2),
+        (CoordinateValue { x: 0, y: 0 }, 3),
+    ];
+    hilbert_sort_cached(&mut items, &hilbert_curve, |item| item.0);
+    let order: Vec<i32> = items.iter().map(|item| item.1).collect();
+    assert_eq!(vec![1, 3, 0, 2], order);
+}
+
+#[test]
+fn hilbert_sort_f64_quantizes_bounding_box() {
+    // Same layout as the second order test, scaled and shifted
+    let mut items = vec![
+        ("d", (30.5, -10.0)),
+        ("b", (10.5, 10.0)),
+        ("a", (0.5, -10.0)),
+        ("c", (30.5, 10.0)),
+        ("x", (f64::NAN, 0.0)),
+    ];
+    hilbert_sort_f64(&mut items, 2, |item| (item.1 .0, item.1 .1 * 1.5));
+    let names: Vec<&str> = items.iter().map(|item| item.0).collect();
+    assert_eq!(vec!["a", "b", "c", "d", "x"], names);
+}
+
+#[test]
+fn hilbert_sort_f64_empty_and_single() {
+    let mut empty: Vec<(f64, f64)> = Vec::new();
+    hilbert_sort_f64(&mut empty, 8, |point| *point);
+    let mut single = vec![(1.0, 2.0)];
+    hilbert_sort_f64(&mut single, 8, |point| *point);
+    assert_eq!(vec![(1.0, 2.0)], single);
+}
@@ -0,0 +1,74 @@
+use hilbert_curve_rust::{
+    from_hilbert_scan, hilbert_scan_positions, to_hilbert_scan, CoordinateValue,
+    GeneralizedHilbertCurve, HilbertCurveAlgorithm, ScanMode,
+};
+
+#[test]
+fn padded_scan_of_square_is_curve_order() {
+    let pixels: Vec<u32> = (0..64).collect();
+    let scan = to_hilbert_scan(&pixels, 8, 8, ScanMode::Padded);
+    let expected: Vec<u32> = HilbertCurveAlgorithm::new(3)
+        .iter()
+        .map(|point| point.y * 8 + point.x)
+        .collect();
+    assert_eq!(expected, scan);
+}
+
+#[test]
+fn round_trip() {
+    for mode in [ScanMode::Padded, ScanMode::Generalized] {
+        for (width, height) in [(1, 1), (5, 3), (3, 5), (40, 25), (17, 64), (100, 1)] {
+            let pixels: Vec<(u8, u16)> = (0..width * height)
+                .map(|position| ((position % 251) as u8, position as u16))
+                .collect();
+            let scan = to_hilbert_scan(&pixels, width, height, mode);
+            assert_eq!(pixels.len(), scan.len());
+            assert_eq!(pixels, from_hilbert_scan(&scan, width, height, mode));
+        }
+    }
+}
+
+#[test]
+fn positions_are_a_permutation() {
+    for mode in [ScanMode::Padded, ScanMode::Generalized] {
+        let mut positions = hilbert_scan_positions(30, 19, mode);
+        positions.sort_unstable();
+        assert_eq!((0..30 * 19).collect::<Vec<u32>>(), positions);
+    }
+}
+
+#[test]
+fn empty_image() {
+    let pixels: Vec<u8> = Vec::new();
+    assert!(to_hilbert_scan(&pixels, 0, 10, ScanMode::Padded).is_empty());
+    assert!(to_hilbert_scan(&pixels, 10, 0, ScanMode::Generalized).is_empty());
+}
+
+#[test]
+fn generalized_curve_steps_to_neighbors() {
+    for (width, height) in [(6, 4), (10, 7), (7, 10), (33, 8), (1, 9), (64, 64)] {
+        let curve = GeneralizedHilbertCurve::new(width, height);
+        let points: Vec<CoordinateValue> = curve.iter().collect();
+        assert_eq!(curve.len(), points.len() as u64);
+        assert_eq!(CoordinateValue { x: 0, y: 0 }, points[0]);
+        let mut diagonal_steps = 0;
+        for pair in points.windows(2) {
+            let dx = pair[0].x.abs_diff(pair[1].x);
+            let dy = pair[0].y.abs_diff(pair[1].y);
+            assert!(dx <= 1 && dy <= 1 && dx + dy > 0);
+            if dx + dy == 2 {
+                diagonal_steps += 1;
+            }
+        }
+        assert!(diagonal_steps <= 1);
+        if width % 2 == 0 && height % 2 == 0 {
+            assert_eq!(0, diagonal_steps);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "The number of pixels must be the width times the height")]
+fn wrong_length() {
+    to_hilbert_scan(&[0u8; 10], 4, 4, ScanMode::Padded);
+}
@@ -0,0 +1,137 @@
+use hilbert_curve_rust::ipv4::{
+    cidr_to_rectangle, ip_to_point, point_to_cidr, point_to_ip, render_heatmap,
+};
+use hilbert_curve_rust::{Colormap, CoordinateValue, HilbertCurveAlgorithm, Rectangle, Rgb};
+use std::net::Ipv4Addr;
+
+#[test]
+fn index_to_point_sixteenth_order_last_index() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(16);
+    let point = hilbert_curve.index_to_point(u32::MAX);
+    assert_eq!(u32::MAX, hilbert_curve.point_to_index(point));
+}
+
+#[test]
+fn ip_to_point_round_trip() {
+    for ip in [
+        Ipv4Addr::new(0, 0, 0, 0),
+        Ipv4Addr::new(10, 1, 2, 3),
+        Ipv4Addr::new(192, 168, 1, 254),
+        Ipv4Addr::new(255, 255, 255, 255),
+    ] {
+        assert_eq!(ip, point_to_ip(ip_to_point(ip)));
+    }
+}
+
+#[test]
+fn ip_to_point_first_addresses() {
+    assert_eq!(
+        CoordinateValue { x: 0, y: 0 },
+        ip_to_point(Ipv4Addr::new(0, 0, 0, 0))
+    );
+    assert_eq!(
+        CoordinateValue { x: 1, y: 0 },
+        ip_to_point(Ipv4Addr::new(0, 0, 0, 1))
+    );
+}
+
+#[test]
+fn cidr_to_rectangle_even_host_bits_is_a_square() {
+    let rectangle = cidr_to_rectangle(Ipv4Addr::new(10, 0, 0, 0), 8);
+    assert_eq!(4096, rectangle.width());
+    assert_eq!(4096, rectangle.height());
+    assert!(rectangle.contains(ip_to_point(Ipv4Addr::new(10, 200, 3, 4))));
+    assert!(!rectangle.contains(ip_to_point(Ipv4Addr::new(11, 0, 0, 0))));
+}
+
+#[test]
+fn cidr_to_rectangle_odd_host_bits_is_a_half_square() {
+    let rectangle = cidr_to_rectangle(Ipv4Addr::new(192, 168, 0, 0), 23);
+    assert_eq!(512, rectangle.area());
+    assert_eq!(32, rectangle.width().max(rectangle.height()));
+    assert_eq!(16, rectangle.width().min(rectangle.height()));
+    for last in [0u8, 77, 255] {
+        assert!(rectangle.contains(ip_to_point(Ipv4Addr::new(192, 168, 0, last))));
+        assert!(rectangle.contains(ip_to_point(Ipv4Addr::new(192, 168, 1, last))));
+    }
+}
+
+#[test]
+fn cidr_to_rectangle_ignores_host_bits() {
+    assert_eq!(
+        cidr_to_rectangle(Ipv4Addr::new(172, 16, 0, 0), 12),
+        cidr_to_rectangle(Ipv4Addr::new(172, 20, 9, 9), 12)
+    );
+}
+
+#[test]
+fn cidr_to_rectangle_whole_space_and_single_address() {
+    assert_eq!(
+        Rectangle::new(0, 0, 65535, 65535),
+        cidr_to_rectangle(Ipv4Addr::new(1, 2, 3, 4), 0)
+    );
+    let ip = Ipv4Addr::new(8, 8, 8, 8);
+    assert_eq!(
+        Rectangle::from_point(ip_to_point(ip)),
+        cidr_to_rectangle(ip, 32)
+    );
+    assert_eq!(65536u64 * 32768, cidr_to_rectangle(ip, 1).area());
+}
+
+#[test]
+#[should_panic(expected = "The prefix must be between 0 and 32")]
+fn cidr_to_rectangle_prefix_too_long() {
+    cidr_to_rectangle(Ipv4Addr::new(1, 2, 3, 4), 33);
+}
+
+#[test]
+fn point_to_cidr_returns_network_address() {
+    let point = ip_to_point(Ipv4Addr::new(10, 1, 2, 3));
+    assert_eq!((Ipv4Addr::new(10, 1, 2, 0), 24), point_to_cidr(point, 24));
+}
+
+#[test]
+fn render_heatmap_per_slash16() {
+    let image = render_heatmap(
+        vec![
+            (Ipv4Addr::new(10, 0, 0, 1), 5),
+            (Ipv4Addr::new(10, 0, 200, 1), 5),
+            (Ipv4Addr::new(192, 168, 1, 1), 1),
+        ],
+        16,
+        Colormap::Grayscale,
+        false,
+        Rgb::new(0, 0, 255),
+    );
+    assert_eq!(256, image.width());
+    let curve = HilbertCurveAlgorithm::new(8);
+    let ten = curve.index_to_point(10 << 8);
+    let private = curve.index_to_point((192 << 8) + 168);
+    assert_eq!(Rgb::WHITE, image.get_pixel(ten.x, ten.y));
+    assert_eq!(Rgb::new(26, 26, 26), image.get_pixel(private.x, private.y));
+    assert_eq!(Rgb::new(0, 0, 255), image.get_pixel(0, 0));
+}
+
+#[test]
+fn render_heatmap_log_scale() {
+    let image = render_heatmap(
+        vec![
+            (Ipv4Addr::new(0, 0, 0, 0), 1),
+            (Ipv4Addr::new(0, 1, 0, 0), 99),
+        ],
+        16,
+        Colormap::Grayscale,
+        true,
+        Rgb::BLACK,
+    );
+    let curve = HilbertCurveAlgorithm::new(8);
+    let low = curve.index_to_point(0);
+    // ln(2) / ln(100) * 255
+    assert_eq!(Rgb::new(38, 38, 38), image.get_pixel(low.x, low.y));
+}
+
+#[test]
+#[should_panic(expected = "The block prefix must be even and at most 24")]
+fn render_heatmap_odd_prefix() {
+    render_heatmap(Vec::new(), 17, Colormap::Viridis, false, Rgb::BLACK);
+}
@@ -0,0 +1,143 @@
+use hilbert_curve_rust::{CoordinateValue, HilbertCell, LinearQuadtree, Rectangle};
+
+fn scattered_points(count: u32) -> Vec<CoordinateValue> {
+    (0..count)
+        .map(|i| CoordinateValue {
+            x: i.wrapping_mul(2654435761) % 64,
+            y: i.wrapping_mul(40503) % 64,
+        })
+        .collect()
+}
+
+fn assert_partition(quadtree: &LinearQuadtree) {
+    let cells: Vec<HilbertCell> = quadtree.leaves().map(|(cell, _)| cell).collect();
+    let mut expected_start = 0u64;
+    for cell in &cells {
+        let range = cell.index_range(quadtree.max_order());
+        assert_eq!(expected_start, u64::from(*range.start()));
+        expected_start = u64::from(*range.end()) + 1;
+    }
+    assert_eq!(u64::pow(4, quadtree.max_order().into()), expected_start);
+}
+
+#[test]
+fn insert_splits_past_capacity() {
+    let mut quadtree = LinearQuadtree::new(6, 4);
+    assert_eq!(1, quadtree.leaf_count());
+    for point in scattered_points(100) {
+        quadtree.insert(point);
+    }
+    assert_eq!(100, quadtree.len());
+    assert!(quadtree.leaf_count() > 1);
+    assert!(quadtree.leaves().all(|(_, points)| points.len() <= 4));
+    assert_partition(&quadtree);
+}
+
+#[test]
+fn insert_same_point_stops_at_max_order() {
+    let mut quadtree = LinearQuadtree::new(3, 2);
+    for _ in 0..5 {
+        quadtree.insert(CoordinateValue { x: 1, y: 1 });
+    }
+    let cell = quadtree.locate(CoordinateValue { x: 1, y: 1 });
+    assert_eq!(3, cell.order());
+    let (_, points) = quadtree.leaves().find(|(leaf, _)| *leaf == cell).unwrap();
+    assert_eq!(5, points.len());
+    assert_partition(&quadtree);
+}
+
+#[test]
+fn locate_returns_leaf_containing_point() {
+    let mut quadtree = LinearQuadtree::new(6, 3);
+    let points = scattered_points(60);
+    for point in &points {
+        quadtree.insert(*point);
+    }
+    for point in points {
+        let cell = quadtree.locate(point);
+        assert!(cell.bounds(6).contains(point));
+        let (_, leaf_points) = quadtree.leaves().find(|(leaf, _)| *leaf == cell).unwrap();
+        assert!(leaf_points.contains(&point));
+    }
+}
+
+#[test]
+fn remove_merges_underfull_siblings() {
+    let mut quadtree = LinearQuadtree::new(6, 4);
+    let points = scattered_points(50);
+    for point in &points {
+        quadtree.insert(*point);
+    }
+    for point in &points {
+        assert!(quadtree.remove(*point));
+        assert_partition(&quadtree);
+    }
+    assert!(quadtree.is_empty());
+    assert_eq!(1, quadtree.leaf_count());
+    assert!(!quadtree.remove(points[0]));
+}
+
+#[test]
+fn query_rect_matches_brute_force() {
+    let mut quadtree = LinearQuadtree::new(6, 5);
+    let points = scattered_points(300);
+    for point in &points {
+        quadtree.insert(*point);
+    }
+    for rectangle in [
+        Rectangle::new(0, 0, 63, 63),
+        Rectangle::new(10, 5, 40, 20),
+        Rectangle::new(63, 63, 100, 100),
+    ] {
+        let mut found = quadtree.query_rect(&rectangle);
+        let mut expected: Vec<CoordinateValue> = points
+            .iter()
+            .copied()
+            .filter(|point| rectangle.contains(*point))
+            .collect();
+        found.sort_by_key(|point| (point.x, point.y));
+        expected.sort_by_key(|point| (point.x, point.y));
+        assert_eq!(expected, found);
+    }
+}
+
+#[test]
+fn balance_limits_neighbor_order_difference() {
+    let mut quadtree = LinearQuadtree::new(6, 1);
+    // Points packed next to the center force deep leaves next to the large leaves of the other quadrants
+    for point in [
+        CoordinateValue { x: 30, y: 30 },
+        CoordinateValue { x: 31, y: 30 },
+        CoordinateValue { x: 30, y: 31 },
+        CoordinateValue { x: 31, y: 31 },
+    ] {
+        quadtree.insert(point);
+    }
+    let before = quadtree.leaf_count();
+    let splits = quadtree.balance();
+    assert!(splits > 0);
+    assert_eq!(before + 3 * splits, quadtree.leaf_count());
+    assert_eq!(4, quadtree.len());
+    assert_partition(&quadtree);
+
+    let leaves: Vec<(HilbertCell, Rectangle)> = quadtree
+        .leaves()
+        .map(|(cell, _)| (cell, cell.bounds(6)))
+        .collect();
+    for (cell, bounds) in &leaves {
+        for (other, other_bounds) in &leaves {
+            let touch_horizontally = (bounds.x_max + 1 == other_bounds.x_min
+                || other_bounds.x_max + 1 == bounds.x_min)
+                && bounds.y_min <= other_bounds.y_max
+                && other_bounds.y_min <= bounds.y_max;
+            let touch_vertically = (bounds.y_max + 1 == other_bounds.y_min
+                || other_bounds.y_max + 1 == bounds.y_min)
+                && bounds.x_min <= other_bounds.x_max
+                && other_bounds.x_min <= bounds.x_max;
+            if touch_horizontally || touch_vertically {
+                assert!(cell.order().abs_diff(other.order()) <= 1);
+            }
+        }
+    }
+    assert_eq!(0, quadtree.balance());
+}
@@ -0,0 +1,84 @@
+use hilbert_curve_rust::{
+    hilbert_sort_cached, par_hilbert_sort, par_indices_to_points, par_points_to_indices,
+    CoordinateValue, HilbertCurveAlgorithm,
+};
+
+fn scattered_points(count: u32, order: u16) -> Vec<CoordinateValue> {
+    let mask = u32::pow(2, order.into()) - 1;
+    (0..count)
+        .map(|i| CoordinateValue {
+            x: i.wrapping_mul(2654435761) & mask,
+            y: i.wrapping_mul(40503).rotate_left(7) & mask,
+        })
+        .collect()
+}
+
+#[test]
+fn par_points_to_indices_matches_serial() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(10);
+    let points = scattered_points(5000, 10);
+    let expected: Vec<u32> = points
+        .iter()
+        .map(|point| hilbert_curve.point_to_index(*point))
+        .collect();
+    for threads in [1, 2, 3, 8] {
+        assert_eq!(
+            expected,
+            par_points_to_indices(&hilbert_curve, &points, threads)
+        );
+    }
+}
+
+#[test]
+fn par_indices_to_points_round_trip() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(6);
+    let indices: Vec<u32> = (0..4096).collect();
+    let points = par_indices_to_points(&hilbert_curve, &indices, 4);
+    assert_eq!(indices, par_points_to_indices(&hilbert_curve, &points, 3));
+}
+
+#[test]
+fn par_hilbert_sort_identical_to_serial() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(12);
+    // Duplicated points check that the order of equal keys is the same as the stable serial sort
+    let items: Vec<(CoordinateValue, usize)> = scattered_points(20000, 5)
+        .into_iter()
+        .chain(scattered_points(20000, 12))
+        .enumerate()
+        .map(|(position, point)| (point, position))
+        .collect();
+    let mut expected = items.clone();
+    hilbert_sort_cached(&mut expected, &hilbert_curve, |item| item.0);
+    for threads in [1, 2, 5, 16] {
+        let mut sorted = items.clone();
+        par_hilbert_sort(&mut sorted, &hilbert_curve, |item| item.0, threads);
+        assert_eq!(expected, sorted);
+    }
+}
+
+#[test]
+fn par_hilbert_sort_more_threads_than_items() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let mut items = vec![
+        CoordinateValue { x: 1, y: 0 },
+        CoordinateValue { x: 0, y: 0 },
+    ];
+    par_hilbert_sort(&mut items, &hilbert_curve, |point| *point, 32);
+    assert_eq!(
+        vec![
+            CoordinateValue { x: 0, y: 0 },
+            CoordinateValue { x: 1, y: 0 }
+        ],
+        items
+    );
+    let mut empty: Vec<CoordinateValue> = Vec::new();
+    par_hilbert_sort(&mut empty, &hilbert_curve, |point| *point, 4);
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The number of threads must be at least one")]
+fn par_points_to_indices_zero_thread() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    par_points_to_indices(&hilbert_curve, &[CoordinateValue { x: 0, y: 0 }], 0);
+}
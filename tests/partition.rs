@@ -0,0 +1,138 @@
+mod common;
+
+use common::random_points;
+use hilbert_curve_rust::{
+    partition, partition_points, rebalance, CoordinateValue, HilbertCurveAlgorithm,
+};
+use std::ops::RangeInclusive;
+
+#[test]
+fn balanced_contiguous_pieces() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(8);
+    let points = random_points(10_000, 256, 1);
+    for pieces in [1, 3, 8, 50] {
+        let partitions = partition_points(&hilbert_curve, &points, pieces);
+        assert_eq!(pieces, partitions.len());
+        assert_eq!(0, *partitions[0].range().start());
+        assert_eq!(65535, *partitions[pieces - 1].range().end());
+        for pair in partitions.windows(2) {
+            assert_eq!(*pair[0].range().end() + 1, *pair[1].range().start());
+        }
+        let total: u64 = partitions.iter().map(|piece| piece.weight()).sum();
+        assert_eq!(10_000, total);
+        let ideal = 10_000 / pieces as u64;
+        // Points sharing a cell cannot be split, and there are few per cell
+        assert!(partitions
+            .iter()
+            .all(|piece| piece.weight().abs_diff(ideal) <= 4));
+    }
+}
+
+#[test]
+fn rectangles_cover_the_range() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(5);
+    let points = random_points(500, 32, 2);
+    for piece in partition_points(&hilbert_curve, &points, 7) {
+        let cells: u64 = piece
+            .rectangles()
+            .iter()
+            .map(|rectangle| rectangle.area())
+            .sum();
+        let range = piece.range();
+        assert_eq!(u64::from(range.end() - range.start()) + 1, cells);
+        for rectangle in piece.rectangles() {
+            for y in rectangle.y_min..=rectangle.y_max {
+                for x in rectangle.x_min..=rectangle.x_max {
+                    assert!(range.contains(&hilbert_curve.point_to_index(CoordinateValue { x, y })));
+                }
+            }
+            assert!(piece.bounding_box().contains_rectangle(rectangle));
+        }
+    }
+}
+
+#[test]
+fn weighted_items() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let items = [(0, 10), (3, 10), (5, 30), (5, 10), (9, 20), (14, 20)];
+    let partitions = partition(&hilbert_curve, &items, 2);
+    assert_eq!(&(0..=8), partitions[0].range());
+    assert_eq!(&(9..=15), partitions[1].range());
+    assert_eq!(
+        vec![60, 40],
+        partitions
+            .iter()
+            .map(|piece| piece.weight())
+            .collect::<Vec<_>>()
+    );
+    let partitions = partition(&hilbert_curve, &items, 3);
+    assert_eq!(
+        vec![20, 40, 40],
+        partitions
+            .iter()
+            .map(|piece| piece.weight())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn more_pieces_than_items() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let partitions = partition(&hilbert_curve, &[(2, 5)], 4);
+    let ranges: Vec<RangeInclusive<u32>> = partitions
+        .iter()
+        .map(|piece| piece.range().clone())
+        .collect();
+    assert_eq!(vec![0..=0, 1..=1, 2..=2, 3..=3], ranges);
+    assert_eq!(4, partition(&hilbert_curve, &[], 4).len());
+}
+
+#[test]
+fn rebalance_moves_few_boundaries() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(8);
+    let points = random_points(8000, 256, 3);
+    let items: Vec<(u32, u64)> = points
+        .iter()
+        .map(|point| (hilbert_curve.point_to_index(*point), 1))
+        .collect();
+    let partitions = partition(&hilbert_curve, &items, 8);
+    let ranges: Vec<RangeInclusive<u32>> = partitions
+        .iter()
+        .map(|piece| piece.range().clone())
+        .collect();
+    let unchanged = rebalance(&hilbert_curve, &ranges, &items, 0.1);
+    assert_eq!(0, unchanged.moved_boundaries);
+    assert_eq!(0, unchanged.moved_weight);
+    assert_eq!(partitions, unchanged.partitions);
+
+    // A burst of items in the first piece only
+    let mut grown = items.clone();
+    grown.extend((0..2000).map(|position| (position % 1000, 1)));
+    let rebalanced = rebalance(&hilbert_curve, &ranges, &grown, 0.1);
+    let ideal = grown.len() as f64 / 8.0;
+    assert!(rebalanced
+        .partitions
+        .iter()
+        .all(|piece| (piece.weight() as f64 - ideal).abs() <= 0.2 * ideal + 2.0));
+    let full = partition(&hilbert_curve, &grown, 8);
+    let moved_to_ideal: u64 = ranges
+        .iter()
+        .zip(&full)
+        .skip(1)
+        .map(|(old, new)| {
+            let (a, b) = (*old.start(), *new.range().start());
+            grown
+                .iter()
+                .filter(|(key, _)| (a.min(b)..a.max(b)).contains(key))
+                .count() as u64
+        })
+        .sum();
+    assert!(rebalanced.moved_weight > 0);
+    assert!(rebalanced.moved_weight < moved_to_ideal);
+}
+
+#[test]
+#[should_panic(expected = "The number of pieces must be between 1 and the number of cells")]
+fn zero_pieces() {
+    partition(&HilbertCurveAlgorithm::new(2), &[], 0);
+}
@@ -0,0 +1,77 @@
+use hilbert_curve_rust::{
+    apply_permutation, hilbert_permutation, invert_permutation, radix_sort_permutation,
+    CoordinateValue, HilbertCurveAlgorithm,
+};
+
+#[test]
+fn hilbert_permutation_second_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let points = vec![
+        CoordinateValue { x: 3, y: 0 },
+        CoordinateValue { x: 1, y: 2 },
+        CoordinateValue { x: 0, y: 0 },
+        CoordinateValue { x: 3, y: 2 },
+    ];
+    assert_eq!(
+        vec![2, 1, 3, 0],
+        hilbert_permutation(&hilbert_curve, &points)
+    );
+}
+
+#[test]
+fn hilbert_permutation_reorders_parallel_columns() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(8);
+    let points: Vec<CoordinateValue> = (0..500u32)
+        .map(|i| CoordinateValue {
+            x: (i * 37) % 256,
+            y: (i * 101) % 256,
+        })
+        .collect();
+    let names: Vec<String> = (0..500).map(|i| format!("row {}", i)).collect();
+    let permutation = hilbert_permutation(&hilbert_curve, &points);
+
+    let sorted_points = apply_permutation(&points, &permutation);
+    let sorted_names = apply_permutation(&names, &permutation);
+    let indices: Vec<u32> = sorted_points
+        .iter()
+        .map(|point| hilbert_curve.point_to_index(*point))
+        .collect();
+    assert!(indices.windows(2).all(|pair| pair[0] <= pair[1]));
+    for (point, name) in sorted_points.iter().zip(&sorted_names) {
+        let row: usize = name[4..].parse().unwrap();
+        assert_eq!(points[row], *point);
+    }
+
+    let restored = apply_permutation(&sorted_names, &invert_permutation(&permutation));
+    assert_eq!(names, restored);
+}
+
+#[test]
+fn radix_sort_permutation_matches_stable_sort() {
+    let keys: Vec<u64> = (0..2000u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (i % 50))
+        .collect();
+    let mut expected: Vec<u32> = (0..keys.len() as u32).collect();
+    expected.sort_by_key(|position| keys[*position as usize]);
+    assert_eq!(expected, radix_sort_permutation(&keys));
+}
+
+#[test]
+fn invert_permutation_round_trip() {
+    let permutation = vec![3, 0, 4, 1, 2];
+    let inverse = invert_permutation(&permutation);
+    assert_eq!(vec![1, 3, 4, 0, 2], inverse);
+    assert_eq!(permutation, invert_permutation(&inverse));
+}
+
+#[test]
+#[should_panic(expected = "The permutation must contain every position exactly once")]
+fn invert_permutation_duplicate_position() {
+    invert_permutation(&[0, 1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "The permutation must have the same length as the items")]
+fn apply_permutation_wrong_length() {
+    apply_permutation(&[1, 2, 3], &[0, 1]);
+}
@@ -0,0 +1,101 @@
+use hilbert_curve_rust::raster::{render_curve, render_values, RasterImage};
+use hilbert_curve_rust::{Colormap, CoordinateValue, HilbertCurveAlgorithm, Rgb};
+
+#[test]
+fn to_ppm_header_and_payload() {
+    let mut image = RasterImage::new(2, 1, Rgb::BLACK);
+    image.set_pixel(1, 0, Rgb::new(1, 2, 3));
+    let bytes = image.to_ppm();
+    let header = b"P6\n2 1\n255\n";
+    assert_eq!(&bytes[..header.len()], header);
+    assert_eq!(&bytes[header.len()..], &[0, 0, 0, 1, 2, 3]);
+}
+
+#[test]
+fn to_pgm_header_and_payload() {
+    let mut image = RasterImage::new(1, 2, Rgb::BLACK);
+    image.set_pixel(0, 1, Rgb::WHITE);
+    let bytes = image.to_pgm();
+    let header = b"P5\n1 2\n255\n";
+    assert_eq!(&bytes[..header.len()], header);
+    assert_eq!(&bytes[header.len()..], &[0, 255]);
+}
+
+#[test]
+fn draw_line_diagonal() {
+    let mut image = RasterImage::new(3, 3, Rgb::BLACK);
+    image.draw_line(
+        CoordinateValue { x: 0, y: 0 },
+        CoordinateValue { x: 2, y: 2 },
+        Rgb::WHITE,
+    );
+    assert_eq!(Rgb::WHITE, image.get_pixel(1, 1));
+    assert_eq!(Rgb::BLACK, image.get_pixel(1, 0));
+}
+
+#[test]
+#[should_panic(expected = "The pixel must be inside the image")]
+fn get_pixel_outside_image() {
+    let image = RasterImage::new(2, 2, Rgb::BLACK);
+    image.get_pixel(2, 0);
+}
+
+#[test]
+fn render_curve_cell_size_one_covers_every_pixel() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    let image = render_curve(&hilbert_curve, 1, Rgb::WHITE, Rgb::BLACK);
+    assert_eq!(8, image.width());
+    assert!(image.pixels().iter().all(|pixel| *pixel == Rgb::WHITE));
+}
+
+#[test]
+fn render_curve_first_order() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let image = render_curve(&hilbert_curve, 4, Rgb::WHITE, Rgb::BLACK);
+    assert_eq!(8, image.width());
+    assert_eq!(8, image.height());
+    // Left, bottom and right side of the path, the top between index 0 and 3 is open.
+    assert_eq!(Rgb::WHITE, image.get_pixel(2, 4));
+    assert_eq!(Rgb::WHITE, image.get_pixel(4, 6));
+    assert_eq!(Rgb::WHITE, image.get_pixel(6, 4));
+    assert_eq!(Rgb::BLACK, image.get_pixel(4, 2));
+    assert_eq!(Rgb::BLACK, image.get_pixel(0, 0));
+}
+
+#[test]
+fn render_values_maps_index_to_cell() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let image = render_values(
+        &hilbert_curve,
+        &[0.0, 1.0, 2.0],
+        Colormap::Grayscale,
+        2,
+        Rgb::new(255, 0, 0),
+    );
+    assert_eq!(Rgb::BLACK, image.get_pixel(0, 0));
+    assert_eq!(Rgb::new(128, 128, 128), image.get_pixel(1, 3));
+    assert_eq!(Rgb::WHITE, image.get_pixel(3, 3));
+    // Index 3 has no value
+    assert_eq!(Rgb::new(255, 0, 0), image.get_pixel(3, 0));
+}
+
+#[test]
+fn render_values_skips_nan() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let image = render_values(
+        &hilbert_curve,
+        &[f64::NAN, 5.0],
+        Colormap::Viridis,
+        1,
+        Rgb::BLACK,
+    );
+    assert_eq!(Rgb::BLACK, image.get_pixel(0, 0));
+    assert_eq!(Colormap::Viridis.color(1.0), image.get_pixel(0, 1));
+}
+
+#[test]
+#[should_panic(expected = "There are more values than cells in the curve. Increase the order.")]
+fn render_values_too_many_values() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    render_values(&hilbert_curve, &[0.0; 5], Colormap::Viridis, 1, Rgb::BLACK);
+}
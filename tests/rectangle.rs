@@ -0,0 +1,44 @@
+use hilbert_curve_rust::{CoordinateValue, Rectangle};
+
+#[test]
+fn rectangle_dimensions_are_inclusive() {
+    let rectangle = Rectangle::new(2, 3, 5, 3);
+    assert_eq!(4, rectangle.width());
+    assert_eq!(1, rectangle.height());
+    assert_eq!(4, rectangle.area());
+}
+
+#[test]
+#[should_panic(
+    expected = "The minimum coordinates must be lower or equal to the maximum coordinates"
+)]
+fn rectangle_inverted_bounds() {
+    Rectangle::new(3, 0, 2, 0);
+}
+
+#[test]
+fn rectangle_contains_borders() {
+    let rectangle = Rectangle::new(1, 1, 2, 2);
+    assert!(rectangle.contains(CoordinateValue { x: 1, y: 1 }));
+    assert!(rectangle.contains(CoordinateValue { x: 2, y: 2 }));
+    assert!(!rectangle.contains(CoordinateValue { x: 3, y: 2 }));
+    assert!(!rectangle.contains(CoordinateValue { x: 0, y: 1 }));
+}
+
+#[test]
+fn rectangle_intersection_touching_and_disjoint() {
+    let rectangle = Rectangle::new(0, 0, 3, 3);
+    assert_eq!(
+        Some(Rectangle::new(3, 3, 3, 3)),
+        rectangle.intersection(&Rectangle::new(3, 3, 5, 5))
+    );
+    assert_eq!(None, rectangle.intersection(&Rectangle::new(4, 0, 5, 5)));
+    assert!(rectangle.contains_rectangle(&Rectangle::new(1, 1, 2, 3)));
+    assert!(!rectangle.contains_rectangle(&Rectangle::new(1, 1, 2, 4)));
+}
+
+#[test]
+fn rectangle_union_is_bounding_box() {
+    let union = Rectangle::new(0, 5, 1, 6).union(&Rectangle::new(4, 0, 4, 1));
+    assert_eq!(Rectangle::new(0, 0, 4, 6), union);
+}
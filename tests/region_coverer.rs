@@ -0,0 +1,157 @@
+use hilbert_curve_rust::{
+    BlockRelation, CoverableRegion, HilbertCell, HilbertRegion, Polygon, Rectangle, RegionCoverer,
+};
+
+fn cells_region(cells: &[HilbertCell], grid_order: u16) -> HilbertRegion {
+    HilbertRegion::from_ranges(
+        grid_order,
+        cells.iter().map(|cell| cell.index_range(grid_order)),
+    )
+}
+
+fn coverer(grid_order: u16, max_cells: usize) -> RegionCoverer {
+    RegionCoverer {
+        grid_order,
+        max_order: grid_order,
+        max_cells,
+        ..RegionCoverer::default()
+    }
+}
+
+#[test]
+fn rectangle_covering_and_interior() {
+    let rectangle = Rectangle::new(100, 200, 700, 450);
+    let exact = HilbertRegion::from_rectangle(10, &rectangle);
+    for max_cells in [1, 4, 8, 20, 100] {
+        let coverer = coverer(10, max_cells);
+        let covering = coverer.covering(&rectangle);
+        assert!(covering.len() <= max_cells);
+        assert!(covering.windows(2).all(|pair| pair[0] < pair[1]));
+        let covered = cells_region(&covering, 10);
+        assert_eq!(exact, exact.intersection(&covered));
+        let interior = coverer.interior_covering(&rectangle);
+        assert!(interior.len() <= max_cells);
+        let inside = cells_region(&interior, 10);
+        assert_eq!(inside, inside.intersection(&exact));
+    }
+}
+
+#[test]
+fn more_cells_give_tighter_coverings() {
+    let rectangle = Rectangle::new(100, 200, 700, 450);
+    let area = |cells: &[HilbertCell]| cells_region(cells, 10).area();
+    let loose = coverer(10, 4).covering(&rectangle);
+    let tight = coverer(10, 50).covering(&rectangle);
+    assert!(area(&tight) < area(&loose));
+    assert!(
+        area(&coverer(10, 4).interior_covering(&rectangle))
+            < area(&coverer(10, 50).interior_covering(&rectangle))
+    );
+}
+
+#[test]
+fn exact_with_enough_cells() {
+    let rectangle = Rectangle::new(3, 5, 12, 9);
+    let coverer = coverer(4, 1000);
+    let exact = HilbertRegion::from_rectangle(4, &rectangle);
+    assert_eq!(exact, cells_region(&coverer.covering(&rectangle), 4));
+    assert_eq!(
+        exact,
+        cells_region(&coverer.interior_covering(&rectangle), 4)
+    );
+}
+
+#[test]
+fn order_limits() {
+    let rectangle = Rectangle::new(20, 20, 100, 60);
+    let coverer = RegionCoverer {
+        grid_order: 8,
+        min_order: 3,
+        max_order: 5,
+        max_cells: 1,
+    };
+    let covering = coverer.covering(&rectangle);
+    assert!(covering.iter().all(|cell| (3..=5).contains(&cell.order())));
+    // The minimum order forces more cells than the limit
+    assert!(covering.len() > 1);
+}
+
+#[test]
+fn polygon_with_hole() {
+    let polygon = Polygon::with_holes(
+        vec![(0.0, 0.0), (64.0, 0.0), (64.0, 64.0), (0.0, 64.0)],
+        vec![vec![(16.0, 16.0), (48.0, 16.0), (48.0, 48.0), (16.0, 48.0)]],
+    );
+    assert_eq!(
+        BlockRelation::Outside,
+        polygon.relation(&Rectangle::new(20, 20, 40, 40))
+    );
+    assert_eq!(
+        BlockRelation::Inside,
+        polygon.relation(&Rectangle::new(0, 0, 15, 63))
+    );
+    assert_eq!(
+        BlockRelation::Partial,
+        polygon.relation(&Rectangle::new(0, 0, 31, 31))
+    );
+    let interior = coverer(6, 100).interior_covering(&polygon);
+    let region = cells_region(&interior, 6);
+    assert_eq!(64 * 64 - 32 * 32, region.area());
+    assert!(!region.intersects(&HilbertRegion::from_rectangle(
+        6,
+        &Rectangle::new(16, 16, 47, 47)
+    )));
+}
+
+#[test]
+fn triangle_covering() {
+    let triangle = Polygon::new(vec![(10.5, 3.0), (200.0, 40.0), (60.0, 230.0)]);
+    let coverer = coverer(8, 12);
+    let covering = cells_region(&coverer.covering(&triangle), 8);
+    let interior = cells_region(&coverer.interior_covering(&triangle), 8);
+    assert_eq!(interior, interior.intersection(&covering));
+    for y in 0..256u32 {
+        for x in 0..256u32 {
+            let point = hilbert_curve_rust::CoordinateValue { x, y };
+            if triangle.contains(f64::from(x) + 0.5, f64::from(y) + 0.5) {
+                assert!(covering.contains_point(point));
+            } else {
+                assert!(!interior.contains_point(point));
+            }
+        }
+    }
+}
+
+#[test]
+fn region_covering() {
+    let region = HilbertRegion::from_rectangle(6, &Rectangle::new(0, 0, 40, 20)).union(
+        &HilbertRegion::from_rectangle(6, &Rectangle::new(50, 50, 60, 63)),
+    );
+    let covering = coverer(6, 10).covering(&region);
+    assert!(covering.len() <= 10);
+    assert_eq!(region, region.intersection(&cells_region(&covering, 6)));
+    let interior = coverer(6, 1000).interior_covering(&region);
+    assert_eq!(region, cells_region(&interior, 6));
+}
+
+#[test]
+fn region_covering_with_default_grid_order() {
+    let region = HilbertRegion::from_rectangle(6, &Rectangle::new(0, 0, 40, 20));
+    let default_coverer = RegionCoverer::default();
+    let covering = default_coverer.covering(&region);
+    assert_eq!(coverer(6, 8).covering(&region), covering);
+    assert!(covering.iter().all(|cell| cell.order() <= 6));
+    assert_eq!(region, region.intersection(&cells_region(&covering, 6)));
+    let interior = RegionCoverer {
+        max_cells: 1000,
+        ..RegionCoverer::default()
+    }
+    .interior_covering(&region);
+    assert_eq!(region, cells_region(&interior, 6));
+}
+
+#[test]
+fn empty_covering() {
+    let polygon = Polygon::new(vec![(-10.0, -10.0), (-5.0, -10.0), (-5.0, -5.0)]);
+    assert!(RegionCoverer::default().covering(&polygon).is_empty());
+}
@@ -0,0 +1,79 @@
+use hilbert_curve_rust::{layout_sequence, Aggregation, CoordinateValue};
+
+#[test]
+fn layout_sequence_picks_smallest_order() {
+    assert_eq!(0, layout_sequence(&[1.0], 8, Aggregation::Sum).order());
+    assert_eq!(1, layout_sequence(&[1.0; 4], 8, Aggregation::Sum).order());
+    assert_eq!(2, layout_sequence(&[1.0; 5], 8, Aggregation::Sum).order());
+    assert_eq!(3, layout_sequence(&[1.0; 64], 8, Aggregation::Sum).order());
+}
+
+#[test]
+fn layout_sequence_one_sample_per_cell() {
+    let data: Vec<f64> = (0..16).map(f64::from).collect();
+    let layout = layout_sequence(&data, 8, Aggregation::Sum);
+    assert_eq!(1, layout.samples_per_cell());
+    assert_eq!(Some(7.0), layout.value(CoordinateValue { x: 1, y: 2 }));
+    assert_eq!(Some(15.0), layout.value(CoordinateValue { x: 3, y: 0 }));
+    assert_eq!(
+        Some(7..8),
+        layout.sample_range(CoordinateValue { x: 1, y: 2 })
+    );
+}
+
+#[test]
+fn layout_sequence_empty_cells_after_the_end() {
+    let layout = layout_sequence(&[1.0, 2.0, 3.0, 4.0, 5.0], 8, Aggregation::Sum);
+    assert_eq!(16, layout.grid().len());
+    assert_eq!(
+        5,
+        layout.grid().iter().filter(|value| value.is_some()).count()
+    );
+    assert_eq!(None, layout.value(CoordinateValue { x: 3, y: 0 }));
+    assert_eq!(None, layout.sample_range(CoordinateValue { x: 3, y: 0 }));
+}
+
+#[test]
+fn layout_sequence_aggregates_when_capped() {
+    let data: Vec<f64> = (0..10).map(f64::from).collect();
+    let sum = layout_sequence(&data, 1, Aggregation::Sum);
+    assert_eq!(3, sum.samples_per_cell());
+    // Index 0: 0 + 1 + 2, index 3: only the sample 9
+    assert_eq!(Some(3.0), sum.value(CoordinateValue { x: 0, y: 0 }));
+    assert_eq!(Some(9.0), sum.value(CoordinateValue { x: 1, y: 0 }));
+    assert_eq!(
+        Some(9..10),
+        sum.sample_range(CoordinateValue { x: 1, y: 0 })
+    );
+
+    let mean = layout_sequence(&data, 1, Aggregation::Mean);
+    assert_eq!(Some(4.0), mean.value(CoordinateValue { x: 0, y: 1 }));
+
+    let max = layout_sequence(&data, 1, Aggregation::Max);
+    assert_eq!(Some(8.0), max.value(CoordinateValue { x: 1, y: 1 }));
+}
+
+#[test]
+fn layout_sequence_sample_to_point_round_trip() {
+    let data = vec![0.5; 1000];
+    let layout = layout_sequence(&data, 3, Aggregation::Mean);
+    assert_eq!(3, layout.order());
+    assert_eq!(16, layout.samples_per_cell());
+    for sample in [0, 15, 16, 500, 999] {
+        let point = layout.sample_to_point(sample);
+        assert!(layout.sample_range(point).unwrap().contains(&sample));
+    }
+}
+
+#[test]
+fn layout_sequence_empty_data() {
+    let layout = layout_sequence(&[], 4, Aggregation::Sum);
+    assert_eq!(0, layout.order());
+    assert_eq!(vec![None], layout.grid());
+}
+
+#[test]
+#[should_panic(expected = "The sample is outside the sequence")]
+fn layout_sequence_sample_outside() {
+    layout_sequence(&[1.0, 2.0], 4, Aggregation::Sum).sample_to_point(2);
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::{random_points, random_values};
+use hilbert_curve_rust::{
+    join_intersecting, join_within_distance, CoordinateValue, HilbertCurveAlgorithm, Rectangle,
+};
+
+fn sorted_points(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    count: usize,
+    seed: u32,
+) -> Vec<(u32, CoordinateValue)> {
+    let mut points: Vec<(u32, CoordinateValue)> =
+        random_points(count, hilbert_curve.number_of_rows(), seed)
+            .into_iter()
+            .map(|point| (hilbert_curve.point_to_index(point), point))
+            .collect();
+    points.sort_unstable_by_key(|(index, _)| *index);
+    points
+}
+
+fn sort_boxes(hilbert_curve: &HilbertCurveAlgorithm, boxes: &mut [Rectangle]) {
+    boxes.sort_by_cached_key(|rectangle| *hilbert_curve.ranges_for_rectangle(rectangle)[0].start());
+}
+
+fn random_boxes(
+    hilbert_curve: &HilbertCurveAlgorithm,
+    count: usize,
+    max_size: u32,
+    seed: u32,
+) -> Vec<Rectangle> {
+    let side = hilbert_curve.number_of_rows();
+    let mut boxes: Vec<Rectangle> = random_values(4 * count, seed)
+        .chunks(4)
+        .map(|values| {
+            let (x, y) = (values[0] % side, values[1] % side);
+            let x_max = (x + values[2] % max_size).min(side - 1);
+            let y_max = (y + values[3] % max_size).min(side - 1);
+            Rectangle::new(x, y, x_max, y_max)
+        })
+        .collect();
+    sort_boxes(hilbert_curve, &mut boxes);
+    boxes
+}
+
+#[test]
+fn distance_join_matches_nested_loop() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(8);
+    let left = sorted_points(&hilbert_curve, 400, 1);
+    let right = sorted_points(&hilbert_curve, 500, 2);
+    for distance in [0, 3, 10] {
+        let mut pairs = Vec::new();
+        join_within_distance(&hilbert_curve, &left, &right, distance, |i, j| {
+            pairs.push((i, j))
+        });
+        pairs.sort_unstable();
+        let mut expected = Vec::new();
+        for (i, (_, a)) in left.iter().enumerate() {
+            for (j, (_, b)) in right.iter().enumerate() {
+                let (dx, dy) = (u64::from(a.x.abs_diff(b.x)), u64::from(a.y.abs_diff(b.y)));
+                if dx * dx + dy * dy <= u64::from(distance * distance) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(expected, pairs);
+    }
+}
+
+#[test]
+fn distance_join_same_point() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(4);
+    let point = CoordinateValue { x: 5, y: 9 };
+    let points = vec![(hilbert_curve.point_to_index(point), point)];
+    let mut pairs = Vec::new();
+    join_within_distance(&hilbert_curve, &points, &points, 0, |i, j| {
+        pairs.push((i, j))
+    });
+    assert_eq!(vec![(0, 0)], pairs);
+}
+
+#[test]
+fn box_join_matches_nested_loop() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(8);
+    let left = random_boxes(&hilbert_curve, 150, 40, 3);
+    let right = random_boxes(&hilbert_curve, 200, 25, 4);
+    let mut pairs = Vec::new();
+    join_intersecting(&hilbert_curve, &left, &right, |i, j| pairs.push((i, j)));
+    pairs.sort_unstable();
+    let mut expected = Vec::new();
+    for (i, a) in left.iter().enumerate() {
+        for (j, b) in right.iter().enumerate() {
+            if a.intersects(b) {
+                expected.push((i, j));
+            }
+        }
+    }
+    assert!(!expected.is_empty());
+    assert_eq!(expected, pairs);
+}
+
+#[test]
+fn box_join_large_boxes() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(6);
+    let mut left = vec![Rectangle::new(0, 0, 63, 63), Rectangle::new(10, 10, 50, 12)];
+    let mut right = vec![Rectangle::new(31, 31, 32, 32), Rectangle::new(60, 0, 63, 5)];
+    sort_boxes(&hilbert_curve, &mut left);
+    sort_boxes(&hilbert_curve, &mut right);
+    let mut pairs = Vec::new();
+    join_intersecting(&hilbert_curve, &left, &right, |i, j| {
+        pairs.push((left[i], right[j]))
+    });
+    assert_eq!(2, pairs.len());
+    assert!(pairs
+        .iter()
+        .all(|(a, _)| *a == Rectangle::new(0, 0, 63, 63)));
+}
+
+#[test]
+#[should_panic(expected = "The points must be sorted by Hilbert index")]
+fn unsorted_right_input() {
+    let point = CoordinateValue { x: 0, y: 0 };
+    join_within_distance(
+        &HilbertCurveAlgorithm::new(2),
+        &[],
+        &[(3, point), (1, point)],
+        1,
+        |_, _| {},
+    );
+}
+
+#[test]
+#[should_panic(expected = "The points must be sorted by Hilbert index")]
+fn unsorted_left_input() {
+    let point = CoordinateValue { x: 0, y: 0 };
+    join_within_distance(
+        &HilbertCurveAlgorithm::new(2),
+        &[(3, point), (1, point)],
+        &[],
+        1,
+        |_, _| {},
+    );
+}
+
+#[test]
+#[should_panic(expected = "The boxes must be sorted by the first Hilbert index of their cells")]
+fn unsorted_boxes() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    // The first box starts at index 0, the second one further on the curve
+    let boxes = [Rectangle::new(4, 4, 5, 5), Rectangle::new(0, 0, 1, 1)];
+    join_intersecting(&hilbert_curve, &boxes, &[], |_, _| {});
+}
+
+#[test]
+#[should_panic(expected = "The boxes must lie inside the grid of the curve")]
+fn box_outside_the_grid() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(3);
+    let inside = [Rectangle::new(0, 0, 7, 7)];
+    let crossing = [Rectangle::new(6, 6, 9, 9)];
+    join_intersecting(&hilbert_curve, &inside, &crossing, |_, _| {});
+}
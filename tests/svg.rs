@@ -0,0 +1,95 @@
+use hilbert_curve_rust::{render_svg, HilbertCurveAlgorithm, Rgb, SvgOptions};
+
+#[test]
+fn render_svg_first_order_default_options() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            size: 100,
+            margin: 0,
+            ..Default::default()
+        },
+    );
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\""));
+    assert!(svg.contains("points=\"25,25 25,75 75,75 75,25\""));
+    assert!(svg.contains("stroke=\"#000000\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn render_svg_margin_shifts_the_path() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            size: 120,
+            margin: 10,
+            ..Default::default()
+        },
+    );
+    assert!(svg.contains("points=\"35,35 35,85 85,85 85,35\""));
+}
+
+#[test]
+fn render_svg_gradient_draws_one_line_per_segment() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            gradient: Some((Rgb::new(0, 0, 0), Rgb::new(255, 255, 255))),
+            ..Default::default()
+        },
+    );
+    assert_eq!(15, svg.matches("<line").count());
+    assert!(svg.contains("stroke=\"#000000\""));
+    assert!(svg.contains("stroke=\"#ffffff\""));
+    assert!(!svg.contains("<polyline"));
+}
+
+#[test]
+fn render_svg_grid_draws_cell_boundaries() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            grid: Some(Rgb::new(200, 200, 200)),
+            ..Default::default()
+        },
+    );
+    assert_eq!(10, svg.matches("<line").count());
+    assert!(svg.contains("stroke=\"#c8c8c8\""));
+}
+
+#[test]
+fn render_svg_highlight_fills_cells_in_range() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(2);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            size: 40,
+            margin: 0,
+            background: None,
+            highlight: Some(4..=7),
+            ..Default::default()
+        },
+    );
+    assert_eq!(4, svg.matches("<rect").count());
+    assert_eq!(2, svg.matches("<polyline").count());
+    assert!(svg.contains("points=\"5,25 5,35 15,35 15,25\""));
+}
+
+#[test]
+fn render_svg_highlight_outside_curve_is_ignored() {
+    let hilbert_curve = HilbertCurveAlgorithm::new(1);
+    let svg = render_svg(
+        &hilbert_curve,
+        SvgOptions {
+            background: None,
+            highlight: Some(10..=20),
+            ..Default::default()
+        },
+    );
+    assert_eq!(0, svg.matches("<rect").count());
+    assert_eq!(1, svg.matches("<polyline").count());
+}
@@ -0,0 +1,93 @@
+use hilbert_curve_rust::texture::{Padding, TextureLayout, TileOrder};
+use hilbert_curve_rust::{CoordinateValue, HilbertCurveAlgorithm};
+
+#[test]
+fn tiles_follow_the_curve() {
+    let layout = TextureLayout::new(8, 8, 2, TileOrder::RowMajor);
+    assert_eq!((4, 4), layout.tile_grid());
+    let pixels: Vec<u32> = (0..64).collect();
+    let swizzled = layout.swizzle(&pixels, 8, Padding::Constant(0));
+    for (slot, tile) in HilbertCurveAlgorithm::new(2).iter().enumerate() {
+        let corner = tile.y * 2 * 8 + tile.x * 2;
+        assert_eq!(
+            &[corner, corner + 1, corner + 8, corner + 9],
+            &swizzled[slot * 4..slot * 4 + 4]
+        );
+    }
+}
+
+#[test]
+fn in_tile_orders() {
+    let pixels: Vec<u32> = (0..16).collect();
+    let column_major = TextureLayout::new(4, 4, 4, TileOrder::ColumnMajor);
+    assert_eq!(
+        vec![0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+        column_major.swizzle(&pixels, 4, Padding::Clamp)
+    );
+    let hilbert = TextureLayout::new(4, 4, 4, TileOrder::Hilbert);
+    let expected: Vec<u32> = HilbertCurveAlgorithm::new(2)
+        .iter()
+        .map(|point| point.y * 4 + point.x)
+        .collect();
+    assert_eq!(expected, hilbert.swizzle(&pixels, 4, Padding::Clamp));
+}
+
+#[test]
+fn edge_padding() {
+    let layout = TextureLayout::new(3, 1, 2, TileOrder::RowMajor);
+    assert_eq!(8, layout.swizzled_len());
+    let pixels = [1u8, 2, 3, 99];
+    assert_eq!(
+        vec![1, 2, 0, 0, 3, 0, 0, 0],
+        layout.swizzle(&pixels, 4, Padding::Constant(0))
+    );
+    assert_eq!(
+        vec![1, 2, 1, 2, 3, 3, 3, 3],
+        layout.swizzle(&pixels, 4, Padding::Clamp)
+    );
+    let positions: Vec<Option<CoordinateValue>> = layout.swizzled_pixels().collect();
+    assert_eq!(Some(CoordinateValue { x: 2, y: 0 }), positions[4]);
+    assert_eq!(None, positions[5]);
+}
+
+#[test]
+fn round_trip_with_stride() {
+    for tile_order in [
+        TileOrder::RowMajor,
+        TileOrder::ColumnMajor,
+        TileOrder::Hilbert,
+    ] {
+        let (width, height, stride) = (37u32, 21u32, 40usize);
+        let source: Vec<u32> = (0..stride as u32 * height).collect();
+        let layout = TextureLayout::new(width, height, 8, tile_order);
+        let swizzled = layout.swizzle(&source, stride, Padding::Clamp);
+        assert_eq!(layout.swizzled_len(), swizzled.len());
+        let mut destination = vec![u32::MAX; source.len()];
+        layout.deswizzle_into(&swizzled, &mut destination, stride);
+        for y in 0..height as usize {
+            for x in 0..stride {
+                let expected = if x < width as usize {
+                    source[y * stride + x]
+                } else {
+                    u32::MAX
+                };
+                assert_eq!(expected, destination[y * stride + x]);
+            }
+        }
+        let tight = layout.deswizzle(&swizzled);
+        assert_eq!(width as usize * height as usize, tight.len());
+        assert_eq!(source[stride + 3], tight[width as usize + 3]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "The tile size must be a power of two")]
+fn tile_size_not_power_of_two() {
+    TextureLayout::new(8, 8, 3, TileOrder::Hilbert);
+}
+
+#[test]
+#[should_panic(expected = "The stride must be at least the width")]
+fn stride_below_width() {
+    TextureLayout::new(8, 8, 4, TileOrder::Hilbert).swizzle(&[0u8; 64], 7, Padding::Clamp);
+}